@@ -1,6 +1,7 @@
 
 export const cache = {
     maps: new Array<MapData>(),
+    heroes: new Array<HeroClassData>(),
     commands: new Array<CommandData>(),
 }
 
@@ -22,6 +23,7 @@ export async function init_cache() {
             console.log("Cache loaded from local storage.");
 
             cache.maps = JSON.parse(local_cache)["maps"];
+            cache.heroes = JSON.parse(local_cache)["heroes"];
             cache.commands = JSON.parse(local_cache)["commands"];
         }
     }
@@ -33,6 +35,7 @@ async function fetch_cache() {
     const json = await fetch("/cache").then((response) => response.json());
 
     cache.maps = json["maps"];
+    cache.heroes = json["heroes"];
     cache.commands = json["commands"];
 
     console.log("Cache loaded.");
@@ -47,6 +50,13 @@ type MapData = {
     text_color: string,
 }
 
+export type HeroClassData = {
+    id: string,
+    name: string,
+    color: string | null,
+    abilities: string[],
+}
+
 export type CommandData = {
     name: string,
     description: string,
@@ -15,6 +15,7 @@ class Leaderboard implements WsModule {
         player_info.on_player_remove.push(this.remove.bind(this));
         player_info.on_player_transfer.push(this.transfer.bind(this));
         player_info.on_player_set_downed.push(this.set_downed.bind(this));
+        player_info.on_player_set_progress.push(this.set_progress.bind(this));
     }
 
     // Leaderboard action handlers
@@ -66,6 +67,12 @@ class Leaderboard implements WsModule {
         }
     }
 
+    private set_progress(player: PlayerData) {
+        for (const map of this.maps.values()) {
+            map.set_progress(player);
+        }
+    }
+
     // Helpers
 
     private add_map(map: LeaderboardMap) {
@@ -169,6 +176,15 @@ class LeaderboardMap {
         }
     }
 
+    set_progress(player: PlayerData) {
+        for (const entry of this.entries) {
+            if (entry.id === player.id) {
+                entry.set_progress(player);
+                break;
+            }
+        }
+    }
+
     private update_list() {
         this.list.textContent = "";
         for (const entry of this.entries) {
@@ -181,6 +197,7 @@ class LeaderboardEntry {
     id: bigint;
     order: number;
     element: HTMLDivElement;
+    private progress_span: HTMLSpanElement;
 
     constructor(player: PlayerData) {
         this.id = player.id;
@@ -198,6 +215,12 @@ class LeaderboardEntry {
         const name_div = document.createElement("div");
         name_div.classList.add("leaderboard-entry-name");
         name_div.appendChild(name_span);
+
+        this.progress_span = document.createElement("span");
+        this.progress_span.classList.add("leaderboard-entry-progress");
+        name_div.appendChild(this.progress_span);
+        this.set_progress(player);
+
         this.element.appendChild(name_div);
 
         const area_div = document.createElement("div");
@@ -213,6 +236,10 @@ class LeaderboardEntry {
 
         this.element.appendChild(area_div);
     }
+
+    set_progress(player: PlayerData) {
+        this.progress_span.textContent = ` Lv ${player.level} · ${player.vp} VP`;
+    }
 }
 
 const leaderboard = new Leaderboard();
@@ -1,4 +1,4 @@
-import { init_cache } from "./cache.js";
+import { cache, init_cache } from "./cache.js";
 import { setup_input } from "./input.js";
 import { MessageHandler, ws_connector, WsModule } from "./ws_connector.js";
 import { BinaryReader } from "./binary_reader.js";
@@ -6,6 +6,7 @@ import { BinaryReader } from "./binary_reader.js";
 const game_container = document.querySelector("#game-container") as HTMLDivElement;
 const connection_panel = document.querySelector("#connection-panel") as HTMLDivElement;
 const connect_button = document.querySelector("#connect-button") as HTMLButtonElement;
+const hero_select = document.querySelector("#hero-select") as HTMLSelectElement;
 const connection_message_display = document.querySelector("#connection-message") as HTMLDivElement;
 
 async function main() {
@@ -22,6 +23,8 @@ async function main() {
     try {
         await init_cache();
 
+        populate_hero_select();
+
         clear_connection_message();
         connect_button.disabled = false;
         connect_button.onclick = handle_connection;
@@ -53,6 +56,13 @@ async function handle_connection() {
         header: "INIT",
         callback: (message: BinaryReader) => {
             const response = message.read_u8();
+
+            if (response !== 0) {
+                console.log(`Received INIT error: ${response}`);
+                post_connect({ message: init_error_message(response) });
+                return;
+            }
+
             const user_id = message.read_u64();
 
             console.log(`Received INIT response: ${response}`);
@@ -65,9 +75,15 @@ async function handle_connection() {
     ws_connector.register_handler(init_handler);
 
     const encoder = new TextEncoder();
+    const class_bytes = encoder.encode(hero_select.value);
     const name_bytes = encoder.encode(name);
 
-    ws_connector.send("INIT", name_bytes);
+    const payload = new Uint8Array(1 + class_bytes.length + name_bytes.length);
+    payload[0] = class_bytes.length;
+    payload.set(class_bytes, 1);
+    payload.set(name_bytes, 1 + class_bytes.length);
+
+    ws_connector.send("INIT", payload);
 
     console.log("Connecting...");
     display_connection_message("Connecting...", "#bfff3f");
@@ -103,6 +119,24 @@ function post_connect(response: "ok" | "already_connected" | "name_invalid" | In
     }
 }
 
+function populate_hero_select() {
+    hero_select.replaceChildren();
+
+    for (const hero of cache.heroes) {
+        const option = document.createElement("option");
+
+        option.value = hero.id;
+        option.textContent = hero.name;
+        option.title = hero.abilities.join(", ");
+
+        if (hero.color !== null) {
+            option.style.color = hero.color;
+        }
+
+        hero_select.appendChild(option);
+    }
+}
+
 function show_game() {
     game_container.classList.remove("hidden");
     connection_panel.classList.add("hidden");
@@ -121,6 +155,15 @@ type InitError = {
     message: string
 }
 
+function init_error_message(status: number): string {
+    switch (status) {
+        case 1: return "The server could not read the connection request";
+        case 2: return "The selected hero class does not exist";
+        case 3: return "The server could not spawn your hero";
+        default: return `Unknown error (${status})`;
+    }
+}
+
 class UiDisconnectModule implements WsModule {
     cleanup() {
         game_container.classList.add("hidden");
@@ -10,6 +10,7 @@ class PlayerInfo implements WsModule {
     public on_player_remove: ((player: PlayerData) => void)[] = [];
     public on_player_transfer: ((player: PlayerData) => void)[] = [];
     public on_player_set_downed: ((player: PlayerData) => void)[] = [];
+    public on_player_set_progress: ((player: PlayerData) => void)[] = [];
 
     constructor() {
         this.players = [];
@@ -56,11 +57,16 @@ class PlayerInfo implements WsModule {
 
         const area_info = this.parse_area_info(data);
 
+        const vp = data.read_u32();
+        const level = data.read_u32();
+
         player_info.players.push({
             id: player_id,
             name: player_name,
             area_info,
             downed,
+            vp,
+            level,
         });
 
         for (const handler of this.on_player_add) {
@@ -103,6 +109,18 @@ class PlayerInfo implements WsModule {
         }
     }
 
+    private handle_set_progress(data: BinaryReader) {
+        const player_id = data.read_u64();
+
+        const player = this.get_player(player_id)!;
+        player.vp = data.read_u32();
+        player.level = data.read_u32();
+
+        for (const handler of this.on_player_set_progress) {
+            handler(player);
+        }
+    }
+
     private handle_init(data: BinaryReader) {
         data.step(1);
 
@@ -142,6 +160,7 @@ class PlayerInfo implements WsModule {
         { header: "PRMV", callback: this.handle_remove.bind(this) },
         { header: "PTRF", callback: this.handle_transfer.bind(this) },
         { header: "PSDN", callback: this.handle_set_downed.bind(this) },
+        { header: "PSVP", callback: this.handle_set_progress.bind(this) },
         { header: "INIT", callback: this.handle_init.bind(this) },
     ];
 
@@ -156,6 +175,8 @@ export type PlayerData = {
     name: string,
     area_info: AreaInfo,
     downed: boolean,
+    vp: number,
+    level: number,
 };
 
 type AreaInfo = {
@@ -108,6 +108,12 @@ function setup_player_input() {
             return;
         } else if (e.key == "Escape" && !chat.focused()) {
             try_execute_command("/reset");
+        } else if ((e.code === "KeyZ" || e.code === "KeyJ") && !e.repeat) {
+            use_ability(0);
+            return;
+        } else if ((e.code === "KeyX" || e.code === "KeyK") && !e.repeat) {
+            use_ability(1);
+            return;
         } else {
             return;
         }
@@ -165,6 +171,10 @@ function setup_player_input() {
     };
 }
 
+function use_ability(slot: number) {
+    ws_connector.send("ABIL", new Uint8Array([slot]));
+}
+
 export function lock_mouse_input() {
     mouse_input_active = false;
 
@@ -0,0 +1,26 @@
+import { BinaryReader } from "./binary_reader.js";
+import { ws_connector, WsModule } from "./ws_connector.js";
+
+const hero_card = document.getElementById("hero-card") as HTMLDivElement;
+
+function display_progression(data: BinaryReader) {
+    const vp = data.read_u32();
+    data.read_u32(); // total experience
+    const level = data.read_u32();
+    const level_experience = data.read_u32();
+    const level_requirement = data.read_u32();
+
+    hero_card.textContent = `Level ${level} (${level_experience}/${level_requirement} XP) · ${vp} VP`;
+}
+
+class ProgressionModule implements WsModule {
+    handlers = [
+        { header: "PROG", callback: display_progression },
+    ]
+
+    cleanup = () => {
+        hero_card.textContent = "Hero";
+    }
+}
+
+ws_connector.register_module(new ProgressionModule());
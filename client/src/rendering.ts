@@ -1,7 +1,7 @@
 import { player_input } from "./player_input.js";
 import Canvas from "./canvas.js";
 import { report_frame_start, report_render_end, report_render_start } from "./metrics.js";
-import { Portal, Rect, RenderNode, Vector2 } from "./types.js";
+import { Portal, RenderNode, Shape, Vector2 } from "./types.js";
 import { BinaryReader } from "./binary_reader.js";
 import { cache } from "./cache.js";
 import { player_info } from "./player_info.js";
@@ -41,6 +41,16 @@ type AreaMessage = {
     color: string,
 }
 
+type DynamicWall = {
+    open: boolean,
+    shape: Shape,
+}
+
+type ColoredShape = {
+    shape: Shape,
+    color: string,
+}
+
 type DrawSettings = {
     fill_color?: string,
     outline_color?: string,
@@ -114,6 +124,20 @@ function draw_arc(canvas: Canvas, _x: number, _y: number, _r: number, start_angl
     }
 }
 
+function draw_shape(canvas: Canvas, shape: Shape, settings: DrawSettings) {
+    switch (shape.kind) {
+        case "rect":
+            draw_rect(canvas, shape.rect.x, shape.rect.y, shape.rect.w, shape.rect.h, settings);
+            break;
+        case "circle":
+            draw_circle(canvas, shape.x, shape.y, shape.radius, settings);
+            break;
+        case "polygon":
+            draw_polygon(canvas, shape.points, settings);
+            break;
+    }
+}
+
 function draw_rect(canvas: Canvas, _x: number, _y: number, _w: number, _h: number, settings: DrawSettings) {
     const ctx = canvas.ctx;
     const { x, y } = canvas.game_to_canvas_pos(_x, _y);
@@ -214,7 +238,43 @@ function drawGrid(width: number, height: number) {
     }
 }
 
-function render_area(width: number, height: number, color: string, walls: Rect[], safeZones: Rect[], portals: Portal[], message: AreaMessage | null) {
+function read_shape(data: BinaryReader): Shape {
+    const kind = data.read_u8();
+
+    switch (kind) {
+        case 1: {
+            const x = data.read_f32();
+            const y = data.read_f32();
+            const radius = data.read_f32();
+
+            return { kind: "circle", x, y, radius };
+        }
+        case 2: {
+            const count = data.read_u16();
+            const points: Vector2[] = [];
+
+            for (let i = 0; i < count; i++) {
+                const x = data.read_f32();
+                const y = data.read_f32();
+
+                points.push({ x, y });
+            }
+
+            return { kind: "polygon", points };
+        }
+        default:
+            return { kind: "rect", rect: data.read_rect() };
+    }
+}
+
+function read_dynamic_wall(data: BinaryReader): DynamicWall {
+    const open = data.read_u8() !== 0;
+    const shape = read_shape(data);
+
+    return { open, shape };
+}
+
+function render_area(width: number, height: number, color: string, walls: Shape[], safeZones: Shape[], switches: Shape[], portals: Portal[], teleporters: Portal[], force_zones: ColoredShape[], surface_zones: ColoredShape[], message: AreaMessage | null) {
     area_canvas.set_dimensions(width * area_canvas.tile_size, height * area_canvas.tile_size);
 
     const ctx = area_canvas.ctx;
@@ -224,21 +284,57 @@ function render_area(width: number, height: number, color: string, walls: Rect[]
 
     drawGrid(width, height);
 
+    for (const zone of surface_zones) {
+        draw_shape(area_canvas, zone.shape, {
+            fill_color: zone.color,
+        });
+    }
+
     for (const wall of walls) {
-        draw_rect(area_canvas, wall.x, wall.y, wall.w, wall.h, {
+        draw_shape(area_canvas, wall, {
             fill_color: "#222",
         });
     }
 
     for (const safeZone of safeZones) {
-        draw_rect(area_canvas, safeZone.x, safeZone.y, safeZone.w, safeZone.h, {
+        draw_shape(area_canvas, safeZone, {
             fill_color: "#00000022",
         });
     }
 
+    for (const zone of force_zones) {
+        draw_shape(area_canvas, zone.shape, {
+            fill_color: zone.color,
+        });
+    }
+
+    for (const pressure_switch of switches) {
+        draw_shape(area_canvas, pressure_switch, {
+            fill_color: "#ffd70066",
+            outline_color: "#b8860b",
+            outline_width: 2,
+        });
+    }
+
     for (const portal of portals) {
-        draw_rect(area_canvas, portal.x, portal.y, portal.w, portal.h, {
-            fill_color: portal.color,
+        if (portal.locked) {
+            draw_rect(area_canvas, portal.x, portal.y, portal.w, portal.h, {
+                fill_color: portal.color,
+                outline_color: "#ff4040",
+                outline_width: 2,
+            });
+        }
+        else {
+            draw_rect(area_canvas, portal.x, portal.y, portal.w, portal.h, {
+                fill_color: portal.color,
+            });
+        }
+    }
+
+    for (const teleporter of teleporters) {
+        draw_rect(area_canvas, teleporter.x, teleporter.y, teleporter.w, teleporter.h, {
+            fill_color: teleporter.color,
+            has_frame: true,
         });
     }
 
@@ -257,7 +353,7 @@ function render_area(width: number, height: number, color: string, walls: Rect[]
 }
 
 
-function render_frame(offset: Vector2, nodes: RenderNode[]) {
+function render_frame(offset: Vector2, nodes: RenderNode[], dynamic_walls: DynamicWall[]) {
     report_render_start();
 
     main_canvas.clear();
@@ -270,6 +366,23 @@ function render_frame(offset: Vector2, nodes: RenderNode[]) {
 
     const self_id = player_info.get_self_id();
 
+    for (const wall of dynamic_walls) {
+        draw_shape(main_canvas, wall.shape, wall.open ? {
+            outline_color: "#222",
+            outline_width: 2,
+        } : {
+            fill_color: "#222",
+        });
+    }
+
+    for (const node of nodes) {
+        if (node.aura !== null) {
+            draw_circle(main_canvas, node.x, node.y, node.aura.radius, {
+                fill_color: node.aura.color,
+            });
+        }
+    }
+
     for (const node of nodes) {
         if (node.is_hero) {
             draw_minimap_hero(node);
@@ -311,10 +424,17 @@ function render_frame(offset: Vector2, nodes: RenderNode[]) {
             }
         }
 
-        draw_ability_overlay(offset, own_hero?.radius);
+        draw_ability_overlay(offset, own_hero?.radius, own_hero?.ability_cooldowns ?? []);
     }
 
     for (const node of heroes) {
+        if (node.protected) {
+            draw_circle(main_canvas, node.x, node.y, node.radius + 0.3, {
+                outline_color: "rgba(255, 255, 255, 0.8)",
+                outline_width: 2,
+            });
+        }
+
         if (node.player_id !== null) {
             const player = player_info.get_player(node.player_id);
 
@@ -333,6 +453,10 @@ function render_frame(offset: Vector2, nodes: RenderNode[]) {
 
             draw_text(main_canvas, node.x, node.y + node.radius + name_height, name, name_color, 16, "bold");
 
+            if (node.death_timer !== null) {
+                draw_text(main_canvas, node.x, node.y, Math.ceil(node.death_timer).toString(), "red", 16, "bold");
+            }
+
         }
     }
 
@@ -343,19 +467,30 @@ function render_frame(offset: Vector2, nodes: RenderNode[]) {
     report_render_end();
 }
 
-function draw_ability_overlay(offset: Vector2, radius: number) {
+function draw_ability_overlay(offset: Vector2, radius: number, cooldowns: number[]) {
 
     const distance = settings.get<number>("visual.ability_overlay.distance");
     const size = settings.get<number>("visual.ability_overlay.size");
 
     if (settings.get<boolean>("visual.ability_overlay.primary") === true) {
         draw_arc(main_canvas, offset.x, offset.y, radius + distance, 100, 210, { outline_width: size, outline_color: "#777777bf" });
-        draw_arc(main_canvas, offset.x, offset.y, radius + distance, 100, 190, { outline_width: size, outline_color: "#7fff7fbf" });
+        draw_ability_arc(offset, radius + distance, 100, 210, cooldowns[0], size);
     }
 
     if (settings.get<boolean>("visual.ability_overlay.secondary") === true) {
         draw_arc(main_canvas, offset.x, offset.y, radius + distance, -30, 80, { outline_width: size, outline_color: "#777777bf" });
-        draw_arc(main_canvas, offset.x, offset.y, radius + distance, 70, 80, { outline_width: size, outline_color: "#7fff7fbf" });
+        draw_ability_arc(offset, radius + distance, -30, 80, cooldowns[1], size);
+    }
+}
+
+function draw_ability_arc(offset: Vector2, radius: number, start: number, end: number, cooldown: number | undefined, size: number) {
+    if (cooldown === undefined) return;
+
+    const color = cooldown === 0 ? "#7fff7fbf" : "#ffbf7fbf";
+    const ready_end = start + (end - start) * (1 - cooldown);
+
+    if (ready_end > start) {
+        draw_arc(main_canvas, offset.x, offset.y, radius, start, ready_end, { outline_width: size, outline_color: color });
     }
 }
 
@@ -429,17 +564,20 @@ function draw_downed_radar(source: Vector2, target: Vector2) {
 
 class RenderingModule implements WsModule {
     private nodes: RenderNode[];
+    private dynamic_walls: DynamicWall[];
 
     private area_name_heading: HTMLHeadingElement;
 
     constructor() {
         this.nodes = [];
+        this.dynamic_walls = [];
         this.area_name_heading = document.querySelector("#area-name") as HTMLHeadingElement;
     }
 
     handlers = [
         { header: "ADEF", callback: this.handle_area_update.bind(this) },
-        { header: "REND", callback: this.handle_render_update.bind(this) }
+        { header: "REND", callback: this.handle_render_update.bind(this) },
+        { header: "WALL", callback: this.handle_wall_update.bind(this) }
     ];
 
     on_game_load = {
@@ -459,18 +597,16 @@ class RenderingModule implements WsModule {
         const safe_zones_length = data.read_u16();
         const portals_length = data.read_u16();
 
-        const walls: Rect[] = [];
-        const safe_zones: Rect[] = [];
+        const walls: Shape[] = [];
+        const safe_zones: Shape[] = [];
         const portals: Portal[] = [];
 
         for (let i = 0; i < walls_length; i++) {
-            const rect = data.read_rect();
-            walls.push(rect);
+            walls.push(read_shape(data));
         }
 
         for (let i = 0; i < safe_zones_length; i++) {
-            const rect = data.read_rect();
-            safe_zones.push(rect);
+            safe_zones.push(read_shape(data));
         }
 
         for (let i = 0; i < portals_length; i++) {
@@ -483,7 +619,9 @@ class RenderingModule implements WsModule {
 
             const color = `rgba(${r}, ${g}, ${b}, ${a / 255})`;
 
-            portals.push({ x, y, w, h, color });
+            const locked = data.read_u8() !== 0;
+
+            portals.push({ x, y, w, h, color, locked });
         }
 
         const [boss, victory, has_custom_text_color] = data.read_flags();
@@ -531,7 +669,71 @@ class RenderingModule implements WsModule {
             }
         }
 
-        render_area(width, height, background_color, walls, safe_zones, portals, message);
+        const dynamic_walls_length = data.read_u16();
+        const switches_length = data.read_u16();
+
+        const switches: Shape[] = [];
+
+        this.dynamic_walls = [];
+
+        for (let i = 0; i < dynamic_walls_length; i++) {
+            this.dynamic_walls.push(read_dynamic_wall(data));
+        }
+
+        for (let i = 0; i < switches_length; i++) {
+            switches.push(read_shape(data));
+        }
+
+        const teleporters_length = data.read_u16();
+        const teleporters: Portal[] = [];
+
+        for (let i = 0; i < teleporters_length; i++) {
+            const { x, y, w, h } = data.read_rect();
+
+            const [r, g, b, a] = data.read_rgba();
+            const color = `rgba(${r}, ${g}, ${b}, ${a / 255})`;
+
+            teleporters.push({ x, y, w, h, color });
+        }
+
+        const force_zones_length = data.read_u16();
+        const force_zones: ColoredShape[] = [];
+
+        for (let i = 0; i < force_zones_length; i++) {
+            const shape = read_shape(data);
+
+            const [r, g, b, a] = data.read_rgba();
+            const color = `rgba(${r}, ${g}, ${b}, ${a / 255})`;
+
+            force_zones.push({ shape, color });
+        }
+
+        const surface_zones_length = data.read_u16();
+        const surface_zones: ColoredShape[] = [];
+
+        for (let i = 0; i < surface_zones_length; i++) {
+            const shape = read_shape(data);
+
+            const [r, g, b, a] = data.read_rgba();
+            const color = `rgba(${r}, ${g}, ${b}, ${a / 255})`;
+
+            surface_zones.push({ shape, color });
+        }
+
+        render_area(width, height, background_color, walls, safe_zones, switches, portals, teleporters, force_zones, surface_zones, message);
+    }
+
+    private handle_wall_update(data: BinaryReader) {
+        const count = data.read_u16();
+
+        for (let i = 0; i < count; i++) {
+            const index = data.read_u16();
+            const wall = read_dynamic_wall(data);
+
+            if (index < this.dynamic_walls.length) {
+                this.dynamic_walls[index] = wall;
+            }
+        }
     }
 
     private handle_render_update(data: BinaryReader) {
@@ -547,7 +749,7 @@ class RenderingModule implements WsModule {
             const [r, g, b, a] = data.read_rgba();
             const color = `rgba(${r}, ${g}, ${b}, ${a / 255})`;
 
-            const [has_outline, is_hero, downed, has_energy] = data.read_flags();
+            const [has_outline, is_hero, downed, has_energy, has_abilities, has_aura, has_death_timer, is_protected] = data.read_flags();
 
             let player_id = null;
             if (is_hero) {
@@ -559,6 +761,26 @@ class RenderingModule implements WsModule {
                 energy = data.read_f32();
             }
 
+            const ability_cooldowns: number[] = [];
+            if (has_abilities) {
+                const ability_count = data.read_u8();
+                for (let j = 0; j < ability_count; j++) {
+                    ability_cooldowns.push(data.read_f32());
+                }
+            }
+
+            let aura = null;
+            if (has_aura) {
+                const aura_radius = data.read_f32();
+                const [r, g, b, a] = data.read_rgba();
+                aura = { radius: aura_radius, color: `rgba(${r}, ${g}, ${b}, ${a / 255})` };
+            }
+
+            let death_timer = null;
+            if (has_death_timer) {
+                death_timer = data.read_f32();
+            }
+
             const node: RenderNode = {
                 x,
                 y,
@@ -567,8 +789,12 @@ class RenderingModule implements WsModule {
                 has_outline,
                 is_hero,
                 downed,
+                protected: is_protected,
                 player_id,
                 energy,
+                ability_cooldowns,
+                aura,
+                death_timer,
             };
 
             if (player_id == player_info.get_self_id()) {
@@ -580,7 +806,7 @@ class RenderingModule implements WsModule {
 
         report_frame_start();
 
-        render_frame(offset, this.nodes);
+        render_frame(offset, this.nodes, this.dynamic_walls);
         this.nodes.length = 0;
     }
 }
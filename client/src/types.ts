@@ -11,12 +11,18 @@ export type Rect = {
     h: number;
 }
 
+export type Shape =
+    | { kind: "rect", rect: Rect }
+    | { kind: "circle", x: number, y: number, radius: number }
+    | { kind: "polygon", points: Vector2[] };
+
 export type Portal = {
     x: number;
     y: number;
     w: number;
     h: number;
     color: string;
+    locked?: boolean;
 }
 
 export type RenderNode = {
@@ -27,6 +33,15 @@ export type RenderNode = {
     has_outline: boolean;
     is_hero: boolean;
     downed: boolean;
+    protected: boolean;
     player_id: bigint | null;
     energy: number | null;
+    ability_cooldowns: number[];
+    aura: Aura | null;
+    death_timer: number | null;
+}
+
+export type Aura = {
+    radius: number;
+    color: string;
 }
\ No newline at end of file
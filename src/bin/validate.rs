@@ -0,0 +1,30 @@
+use colored::Colorize;
+use evadesplus::game::map_validation::{Severity, validate_maps};
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let issues = validate_maps();
+
+    for issue in &issues {
+        let line = issue.to_string();
+
+        match issue.severity {
+            Severity::Error => println!("{}", line.red()),
+            Severity::Warning => println!("{}", line.yellow()),
+        }
+    }
+
+    let errors = issues
+        .iter()
+        .filter(|issue| issue.severity == Severity::Error)
+        .count();
+    let warnings = issues.len() - errors;
+
+    println!("{errors} errors, {warnings} warnings");
+
+    if errors > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
@@ -2,7 +2,10 @@ use std::hash::{DefaultHasher, Hash, Hasher};
 
 use serde::Serialize;
 
-use crate::{game::map::MapTemplate, networking::commands::get_command_cache};
+use crate::{
+    game::{hero_class::HeroClass, map::MapTemplate},
+    networking::commands::get_command_cache,
+};
 
 #[derive(Serialize, Clone, Hash)]
 pub struct MapCache {
@@ -23,6 +26,29 @@ impl MapCache {
     }
 }
 
+#[derive(Serialize, Clone, Hash)]
+pub struct HeroClassCache {
+    id: String,
+    name: String,
+    color: Option<String>,
+    abilities: Vec<String>,
+}
+
+impl HeroClassCache {
+    pub fn new(class: &HeroClass) -> Self {
+        Self {
+            id: class.id.clone(),
+            name: class.name.clone(),
+            color: class.color.as_ref().map(|color| color.to_hex()),
+            abilities: class
+                .abilities
+                .iter()
+                .map(|ability| ability.name().to_owned())
+                .collect(),
+        }
+    }
+}
+
 #[derive(Serialize, Clone, Hash)]
 pub struct CommandCache {
     name: String,
@@ -50,22 +76,29 @@ impl CommandCache {
 #[derive(Serialize, Clone)]
 pub struct Cache {
     maps: Vec<MapCache>,
+    heroes: Vec<HeroClassCache>,
     commands: Vec<CommandCache>,
 }
 
 impl Cache {
-    pub fn new(map_data: Vec<&MapTemplate>) -> Self {
+    pub fn new(map_data: Vec<&MapTemplate>, hero_data: Vec<&HeroClass>) -> Self {
         let maps = map_data.into_iter().map(MapCache::new).collect();
+        let heroes = hero_data.into_iter().map(HeroClassCache::new).collect();
 
         let commands = get_command_cache();
 
-        Self { maps, commands }
+        Self {
+            maps,
+            heroes,
+            commands,
+        }
     }
 
     pub fn get_hash(&self) -> String {
         let mut hasher = DefaultHasher::new();
 
         self.maps.hash(&mut hasher);
+        self.heroes.hash(&mut hasher);
         self.commands.hash(&mut hasher);
 
         format!("{:x}", hasher.finish())
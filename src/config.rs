@@ -19,6 +19,7 @@ fn init_config() -> Config {
 pub struct Config {
     pub network: NetworkConfig,
     pub maps: MapConfig,
+    pub heroes: HeroConfig,
     pub game: GameConfig,
     pub logger: LoggerConfig,
 }
@@ -41,9 +42,23 @@ pub struct MapConfig {
     pub maps: Vec<String>,
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct HeroConfig {
+    pub path: String,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct GameConfig {
     pub simulation_framerate: f32,
+    pub render_framerate: f32,
+    pub max_catch_up_steps: u32,
+    pub spatial_hash_cell_size: f32,
+    /// Seconds an area stays loaded after its last hero leaves.
+    pub area_grace_period: f32,
+    /// Stops simulating areas without heroes while they wait to be closed.
+    pub pause_empty_areas: bool,
+    /// Seconds heroes can't be downed for after spawning, resetting or changing areas.
+    pub spawn_protection: f32,
     pub spawn_map: Option<String>,
 }
 
@@ -41,3 +41,14 @@ where
         }
     }
 }
+
+impl EffectAction<f32> {
+    /// Like `apply`, but receptivity scales how much a multiplier changes the value rather
+    /// than the multiplier itself, so 0 receptivity ignores it and 0.5 halves its effect.
+    pub fn apply_scaled(&self, value: &mut f32, receptivity: f32) {
+        match *self {
+            EffectAction::Multiply(applied) => *value *= 1.0 + (applied - 1.0) * receptivity,
+            _ => self.apply(value, receptivity),
+        }
+    }
+}
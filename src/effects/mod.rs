@@ -28,14 +28,14 @@ where
         id: &'static str,
         priority: EffectPriority,
         action: EffectAction<T::EffectValue, T::EffectAdd, T::EffectMul>,
-        ignore_receptivity: bool,
+        use_receptivity: bool,
     ) -> Self {
         Self::Togglable(Some(TogglableEffect::apply(
             target_list,
             ID_TABLE.get(id),
             priority,
             action,
-            ignore_receptivity,
+            use_receptivity,
         )))
     }
 
@@ -44,14 +44,14 @@ where
         id: String,
         priority: EffectPriority,
         action: EffectAction<T::EffectValue, T::EffectAdd, T::EffectMul>,
-        ignore_receptivity: bool,
+        use_receptivity: bool,
     ) -> Self {
         Self::Togglable(Some(TogglableEffect::apply(
             target_list,
             ID_TABLE.get_or_insert(id),
             priority,
             action,
-            ignore_receptivity,
+            use_receptivity,
         )))
     }
 
@@ -60,7 +60,7 @@ where
         id: &'static str,
         priority: EffectPriority,
         action: EffectAction<T::EffectValue, T::EffectAdd, T::EffectMul>,
-        ignore_receptivity: bool,
+        use_receptivity: bool,
         duration: Duration,
     ) -> Self {
         Self::Timed(TimedEffect::apply(
@@ -68,7 +68,7 @@ where
             ID_TABLE.get(id),
             priority,
             action,
-            ignore_receptivity,
+            use_receptivity,
             duration,
         ))
     }
@@ -78,7 +78,7 @@ where
         id: String,
         priority: EffectPriority,
         action: EffectAction<T::EffectValue, T::EffectAdd, T::EffectMul>,
-        ignore_receptivity: bool,
+        use_receptivity: bool,
         duration: Duration,
     ) -> Self {
         Self::Timed(TimedEffect::apply(
@@ -86,7 +86,7 @@ where
             ID_TABLE.get_or_insert(id),
             priority,
             action,
-            ignore_receptivity,
+            use_receptivity,
             duration,
         ))
     }
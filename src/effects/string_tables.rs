@@ -5,11 +5,24 @@ use arc_swap::ArcSwap;
 use super::core_types::{EffectId, PriorityGroup};
 
 pub static GROUP_TABLE: StaticStringTable<PriorityGroup> = StaticStringTable::new(
-    || ArcSwap::new(HashMap::from([]).into()),
+    || ArcSwap::new(HashMap::from([("ability", 0), ("aura", 1)]).into()),
     |group| unreachable!("Effect group '{group}' not found in initial group table"),
 );
 pub static ID_TABLE: StaticStringTable<EffectId> = StaticStringTable::new(
-    || ArcSwap::new(HashMap::from([]).into()),
+    || {
+        ArcSwap::new(
+            HashMap::from([
+                ("speed_boost", 0),
+                ("invulnerability", 1),
+                ("enemy_slow", 2),
+                ("slowing_aura", 3),
+                ("freezing_aura", 4),
+                ("draining_aura", 5),
+                ("enlarging_aura", 6),
+            ])
+            .into(),
+        )
+    },
     |id| unreachable!("Effect id '{id}' not found in initial id table"),
 );
 
@@ -4,7 +4,7 @@ use super::{
 };
 use arc_swap::ArcSwap;
 use std::{
-    sync::{mpsc, Arc, OnceLock, Weak},
+    sync::{Arc, OnceLock, Weak, mpsc},
     time::Duration,
 };
 use tokio::task::JoinHandle;
@@ -27,10 +27,10 @@ where
         id: EffectId,
         priority: EffectPriority,
         action: EffectAction<T::EffectValue, T::EffectAdd, T::EffectMul>,
-        ignore_receptivity: bool,
+        use_receptivity: bool,
         duration: Duration,
     ) -> Weak<Self> {
-        let effect = Arc::new(ArcSwap::new(Arc::new((ignore_receptivity, action))));
+        let effect = Arc::new(ArcSwap::new(Arc::new((use_receptivity, action))));
         let new = Self {
             targets: target_list
                 .iter_mut()
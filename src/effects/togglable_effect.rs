@@ -4,7 +4,7 @@ use super::{
     core_types::{EffectAction, EffectId, EffectMain, EffectPriority, EffectStore, UpdateEffects},
     target::EffectTarget,
 };
-use std::sync::{mpsc, Arc};
+use std::sync::{Arc, mpsc};
 
 pub struct TogglableEffect<T>
 where
@@ -23,9 +23,9 @@ where
         id: EffectId,
         priority: EffectPriority,
         action: EffectAction<T::EffectValue, T::EffectAdd, T::EffectMul>,
-        ignore_receptivity: bool,
+        use_receptivity: bool,
     ) -> Self {
-        let effect = Arc::new(ArcSwap::new(Arc::new((ignore_receptivity, action))));
+        let effect = Arc::new(ArcSwap::new(Arc::new((use_receptivity, action))));
         let new = Self {
             targets: target_list
                 .iter_mut()
@@ -0,0 +1,162 @@
+use crate::{
+    effects::{Effect, core_types::bool_ops::BoolOps, target::EffectTarget},
+    value::Value,
+};
+use serde::Deserialize;
+
+#[derive(Clone, Copy)]
+pub enum AbilityKind {
+    SpeedBoost {
+        multiplier: f32,
+        duration: f32,
+    },
+    Invulnerability {
+        duration: f32,
+    },
+    Revive {
+        radius: f32,
+    },
+    EnemySlow {
+        radius: f32,
+        multiplier: f32,
+        duration: f32,
+    },
+}
+
+#[derive(Clone, Copy)]
+pub struct Ability {
+    pub kind: AbilityKind,
+    pub energy_cost: f32,
+    pub cooldown: f32,
+}
+
+impl Ability {
+    pub fn new(data: AbilityData) -> Self {
+        match data {
+            AbilityData::SpeedBoost => Self::speed_boost(),
+            AbilityData::Invulnerability => Self::invulnerability(),
+            AbilityData::Revive => Self::revive(),
+            AbilityData::EnemySlow => Self::enemy_slow(),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self.kind {
+            AbilityKind::SpeedBoost { .. } => "Speed Boost",
+            AbilityKind::Invulnerability { .. } => "Invulnerability",
+            AbilityKind::Revive { .. } => "Revive",
+            AbilityKind::EnemySlow { .. } => "Enemy Slow",
+        }
+    }
+
+    pub fn speed_boost() -> Self {
+        Self {
+            kind: AbilityKind::SpeedBoost {
+                multiplier: 1.5,
+                duration: 3.0,
+            },
+            energy_cost: 30.0,
+            cooldown: 6.0,
+        }
+    }
+
+    pub fn invulnerability() -> Self {
+        Self {
+            kind: AbilityKind::Invulnerability { duration: 2.0 },
+            energy_cost: 50.0,
+            cooldown: 12.0,
+        }
+    }
+
+    pub fn revive() -> Self {
+        Self {
+            kind: AbilityKind::Revive { radius: 5.0 },
+            energy_cost: 60.0,
+            cooldown: 10.0,
+        }
+    }
+
+    pub fn enemy_slow() -> Self {
+        Self {
+            kind: AbilityKind::EnemySlow {
+                radius: 6.0,
+                multiplier: 0.5,
+                duration: 3.0,
+            },
+            energy_cost: 40.0,
+            cooldown: 8.0,
+        }
+    }
+}
+
+pub struct AbilitySlot {
+    pub ability: Ability,
+    pub cooldown: f32,
+    pub requested: bool,
+}
+
+impl AbilitySlot {
+    pub fn new(ability: Ability) -> Self {
+        Self {
+            ability,
+            cooldown: 0.0,
+            requested: false,
+        }
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.cooldown <= 0.0
+    }
+
+    /// Remaining cooldown as a fraction of the full cooldown, 0 meaning the ability is ready.
+    pub fn cooldown_fraction(&self) -> f32 {
+        if self.ability.cooldown <= 0.0 {
+            return 0.0;
+        }
+
+        (self.cooldown / self.ability.cooldown).clamp(0.0, 1.0)
+    }
+}
+
+/// Effect of an ability that's dropped once `remaining` seconds of simulation time have passed.
+pub struct TimedAbilityEffect<T>
+where
+    T: EffectTarget + 'static,
+{
+    pub remaining: f32,
+    pub effect: Effect<T>,
+}
+
+/// Ability effects currently applied to an entity, counted down by `system_abilities`.
+#[derive(Default)]
+pub struct AbilityEffects {
+    pub speed: Vec<TimedAbilityEffect<Value<f32>>>,
+    pub invulnerability: Vec<TimedAbilityEffect<Value<bool, BoolOps, BoolOps>>>,
+}
+
+impl AbilityEffects {
+    pub fn tick(&mut self, delta_time: f32) {
+        tick_effects(&mut self.speed, delta_time);
+        tick_effects(&mut self.invulnerability, delta_time);
+    }
+}
+
+fn tick_effects<T: EffectTarget>(effects: &mut Vec<TimedAbilityEffect<T>>, delta_time: f32) {
+    for effect in effects.iter_mut() {
+        effect.remaining -= delta_time;
+    }
+
+    effects.retain(|effect| effect.remaining > 0.0);
+}
+
+pub fn default_abilities() -> Vec<Ability> {
+    vec![Ability::speed_boost(), Ability::invulnerability()]
+}
+
+#[derive(Deserialize)]
+pub enum AbilityData {
+    SpeedBoost,
+    Invulnerability,
+    Revive,
+    EnemySlow,
+}
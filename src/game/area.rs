@@ -1,23 +1,54 @@
 use super::{
+    abilities::AbilityEffects,
+    aura::{ActiveAuras, Aura, AuraData},
+    boss::{BossConfig, BossData, BossFight},
     components::{
-        BounceOffBounds, Bounded, Color, Direction, Enemy, Hero, Position, Size, Speed, Timer,
-        Velocity,
+        BounceOffBounds, Bounded, Color, Direction, Enemy, Harmless, Hero, Position, Size, Speed,
+        Timer, Velocity, WallBounded, WrapAround,
     },
+    dynamic_wall::{DynamicWall, DynamicWallData, PressureSwitch, PressureSwitchData},
+    enemy_behavior::{EnemyBehavior, EnemyBehaviorData},
+    enemy_spawn::{
+        MAX_SPAWN_ATTEMPTS, SpawnDirection, SpawnDirectionData, SpawnObstacles, StatRange,
+        StatRangeData,
+    },
+    force_zone::{ForceZone, ForceZoneData},
+    hero_class::HeroClass,
     portal::{Portal, PortalCreationContext, PortalData},
+    projectile::{ProjectileSpec, ShooterConfig, ShooterData},
+    spawner::{Spawner, SpawnerConfig, SpawnerData},
+    surface::{Inertia, InertiaData, SurfaceZone, SurfaceZoneData},
+    teleporter::{Teleporter, TeleporterData},
 };
 use crate::{
     game::{
-        components::{Energy, MaxEnergy, Regen, SafeZoneBounded, TargetPosition},
-        game::PlayerStatusMessage,
+        components::{
+            Abilities, Boss, Energy, HeroClassId, HeroProgress, Invulnerable, Lifetime, MaxEnergy,
+            Momentum, Projectile, Regen, SafeZoneBounded, Shooter, Spawned, TargetPosition,
+        },
+        game::{PlayerStatusMessage, PortalRefusedMessage},
         transfer_request::TransferRequest,
     },
+    logger::Logger,
     networking::rendering::{AreaRenderMessage, AreaRenderPacket},
-    physics::{rect::Rect, vec2::Vec2},
+    physics::{
+        rect::Rect,
+        shape::{Shape, ShapeData},
+        spatial_hash::SpatialHash,
+        vec2::Vec2,
+    },
 };
 use anyhow::Result;
-use hecs::{Entity, TakenEntity, World};
+use arc_swap::ArcSwap;
+use hecs::{Entity, EntityBuilder, TakenEntity, World};
+use rand::{Rng, SeedableRng, rngs::StdRng};
 use serde::Deserialize;
-use tokio::{sync::mpsc, task::AbortHandle};
+use std::{collections::HashSet, sync::Arc};
+use tokio::sync::mpsc;
+
+/// Areas that don't let heroes in right now, shared so portals in other areas can refuse
+/// heroes without asking the target area.
+pub type LockedAreas = Arc<ArcSwap<HashSet<AreaKey>>>;
 
 pub struct Area {
     pub key: AreaKey,
@@ -36,14 +67,30 @@ pub struct Area {
 
     pub world: World,
 
+    pub enemies: SpatialHash,
+    pub heroes: SpatialHash,
+
     pub bounds: Rect,
     pub spawn_pos: Vec2,
 
-    pub inner_walls: Vec<Rect>,
-    pub safe_zones: Vec<Rect>,
+    pub inner_walls: Vec<Shape>,
+    pub dynamic_walls: Vec<DynamicWall>,
+    pub switches: Vec<PressureSwitch>,
+    pub force_zones: Vec<ForceZone>,
+    /// Movement model applied to heroes anywhere in the area outside of surface zones.
+    pub surface: Option<Inertia>,
+    pub surface_zones: Vec<SurfaceZone>,
+    pub safe_zones: Vec<Shape>,
     pub portals: Vec<Portal>,
+    pub teleporters: Vec<Teleporter>,
 
     pub flags: AreaFlags,
+    pub boss: Option<BossFight>,
+    pub spawners: Vec<Spawner>,
+    pub death_timer: Option<f32>,
+
+    pub seed: u64,
+    pub rng: StdRng,
 
     pub frame_count: u32,
     pub time: f32,
@@ -51,20 +98,26 @@ pub struct Area {
 
     pub render_packet: Option<AreaRenderPacket>,
 
-    pub loop_handle: Option<AbortHandle>,
-
-    pub transfer_tx: mpsc::Sender<TransferRequest>,
+    pub transfer_tx: mpsc::UnboundedSender<TransferRequest>,
     pub render_tx: mpsc::Sender<AreaRenderMessage>,
     pub status_tx: mpsc::Sender<PlayerStatusMessage>,
+    pub refusal_tx: mpsc::Sender<PortalRefusedMessage>,
+    pub locked_areas: LockedAreas,
 }
 
 impl Area {
     pub fn new(
         template: &AreaTemplate,
-        transfer_tx: mpsc::Sender<TransferRequest>,
+        spatial_hash_cell_size: f32,
+        transfer_tx: mpsc::UnboundedSender<TransferRequest>,
         render_tx: mpsc::Sender<AreaRenderMessage>,
         status_tx: mpsc::Sender<PlayerStatusMessage>,
+        refusal_tx: mpsc::Sender<PortalRefusedMessage>,
+        locked_areas: LockedAreas,
     ) -> Self {
+        let bounds = Rect::new(0.0, 0.0, template.width, template.height);
+        let seed = template.seed.unwrap_or_else(rand::random);
+
         let mut area = Self {
             key: template.key.clone(),
             alias: template.alias.clone(),
@@ -79,111 +132,315 @@ impl Area {
             vp: template.vp,
             route_name: template.route_name.clone(),
 
-            bounds: Rect::new(0.0, 0.0, template.width, template.height),
+            enemies: SpatialHash::new(&bounds, spatial_hash_cell_size),
+            heroes: SpatialHash::new(&bounds, spatial_hash_cell_size),
+            bounds,
             spawn_pos: template.spawn_pos,
             inner_walls: template.inner_walls.clone(),
+            dynamic_walls: template.dynamic_walls.clone(),
+            switches: template.switches.clone(),
+            force_zones: template.force_zones.clone(),
+            surface: template.surface,
+            surface_zones: template.surface_zones.clone(),
             safe_zones: template.safe_zones.clone(),
             portals: template.portals.clone(),
+            teleporters: template.teleporters.clone(),
             flags: template.flags.clone(),
+            boss: template.boss.clone().map(BossFight::new),
+            spawners: template
+                .spawners
+                .iter()
+                .cloned()
+                .map(Spawner::new)
+                .collect(),
+            death_timer: template.death_timer,
             world: World::new(),
 
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+
             frame_count: 0,
             time: 0.0,
             delta_time: 0.0,
             render_packet: None,
-            loop_handle: None,
             transfer_tx,
             render_tx,
             status_tx,
+            refusal_tx,
+            locked_areas,
         };
 
         for group in &template.enemy_groups {
             area.spawn_enemy_group(group);
         }
 
+        area.spawn_boss_enemies();
+
         area
     }
 
-    fn close(&mut self) {
-        if let Some(handle) = self.loop_handle.take() {
-            handle.abort();
+    /// Spawns the enemies of the boss fight in their first phase, if the area has one.
+    pub fn spawn_boss_enemies(&mut self) {
+        let Some(enemies) = self.boss.as_ref().map(|boss| boss.config.enemies.clone()) else {
+            return;
+        };
+
+        for enemy in enemies {
+            for entity in self.spawn_enemy_group(&enemy.group) {
+                let _ = self.world.insert_one(
+                    entity,
+                    Boss {
+                        phases: enemy.phases.clone(),
+                        next_phase: 0,
+                    },
+                );
+            }
         }
     }
 
-    pub fn spawn_enemy_group(&mut self, group: &EnemyGroup) {
-        let enemies = (0..group.count).map(|_| {
-            let size = Size(group.size);
+    pub fn hero_count(&mut self) -> usize {
+        self.world.query_mut::<&Hero>().into_iter().count()
+    }
 
-            let mut pos = Position(self.bounds.random_inside());
+    /// Spawns as many enemies of the group as fit. Stops at the first one without a free spot.
+    pub fn spawn_enemy_group(&mut self, group: &EnemyGroup) -> Vec<Entity> {
+        let bounds = self.bounds.clone();
+        let mut entities = Vec::new();
+
+        for _ in 0..group.count {
+            match self.spawn_enemy(group, &bounds) {
+                Ok(entity) => entities.push(entity),
+                Err(err) => {
+                    Logger::error(err.to_string());
+                    break;
+                }
+            }
+        }
 
-            while self
-                .safe_zones
-                .iter()
-                .chain(self.inner_walls.iter())
-                .any(|zone| zone.contains_circle(pos.0, size.0))
-            {
-                pos = Position(self.bounds.random_inside());
+        entities
+    }
+
+    /// Runs one wave of the spawner at the given index, respecting its alive limit.
+    pub fn spawn_wave(&mut self, index: usize) {
+        let config = self.spawners[index].config.clone();
+
+        let alive = self
+            .world
+            .query_mut::<&Spawned>()
+            .into_iter()
+            .filter(|(_, spawned)| spawned.spawner == index)
+            .count() as u32;
+
+        let count = match config.max_alive {
+            Some(max_alive) => config.group.count.min(max_alive.saturating_sub(alive)),
+            None => config.group.count,
+        };
+
+        let region = config.region.unwrap_or_else(|| self.bounds.clone());
+
+        for _ in 0..count {
+            let entity = match self.spawn_enemy(&config.group, &region) {
+                Ok(entity) => entity,
+                Err(err) => {
+                    Logger::error(err.to_string());
+                    break;
+                }
+            };
+
+            let _ = self.world.insert_one(entity, Spawned { spawner: index });
+
+            if let Some(lifetime) = config.lifetime {
+                let _ = self.world.insert_one(entity, Lifetime(lifetime));
             }
+        }
+    }
+
+    /// Enemies are spawned in the part of the group's spawn region that's inside `region`,
+    /// or anywhere in `region` if the group has none. Fails when no spot outside safe zones
+    /// and walls is found.
+    fn spawn_enemy(&mut self, group: &EnemyGroup, region: &Rect) -> Result<Entity> {
+        let speed = group.speed.roll(&mut self.rng);
+        let size = group.size.roll(&mut self.rng);
+
+        let obstacles = SpawnObstacles {
+            safe_zones: &self.safe_zones,
+            inner_walls: &self.inner_walls,
+            dynamic_walls: &self.dynamic_walls,
+        };
+
+        let pos = obstacles
+            .find_position(group, region, size, &mut self.rng)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Could not find a free spot for an enemy of size {size} in area {} after {MAX_SPAWN_ATTEMPTS} attempts",
+                    self.key
+                )
+            })?;
+
+        let mut builder = EntityBuilder::new();
+
+        builder.add_bundle((
+            Enemy,
+            Velocity(Vec2::ZERO),
+            Direction(group.direction.pick(&mut self.rng)),
+            Speed::with_receptivity(speed, group.receptivity),
+            group.color.clone(),
+        ));
 
-            let vel = Velocity(Vec2::ZERO);
-            let dir = Direction(Vec2::random_unit());
-            let speed = Speed(group.speed);
-            let color = group.color.clone();
-
-            (
-                Enemy,
-                TargetPosition(pos.0),
-                pos,
-                vel,
-                dir,
-                speed,
-                size,
-                color,
-                Bounded,
-                BounceOffBounds,
-                SafeZoneBounded,
-            )
+        match group.flags.wrap_around {
+            true => builder.add(WrapAround),
+            false => builder.add(Bounded),
+        };
+
+        if !group.flags.pass_through_walls {
+            builder.add(WallBounded);
+        }
+
+        if !group.flags.enter_safe_zones {
+            builder.add(SafeZoneBounded);
+        }
+
+        if group.flags.harmless {
+            builder.add(Harmless);
+        }
+
+        let pos = group.behavior.add_components(
+            &mut builder,
+            pos,
+            size / 2.0,
+            &self.bounds,
+            |pos| obstacles.is_free(group, pos, size),
+            &mut self.rng,
+        );
+
+        if group.behavior.bounces_off_bounds() && !group.flags.wrap_around {
+            builder.add(BounceOffBounds);
+        }
+
+        if let Some(aura) = &group.aura {
+            builder.add(aura.clone());
+        }
+
+        if let Some(config) = &group.shooter {
+            builder.add(Shooter {
+                config: config.clone(),
+                timer: self.rng.random::<f32>() * config.interval,
+                angle: 0.0,
+            });
+        }
+
+        builder.add_bundle((
+            Position(pos),
+            TargetPosition(pos),
+            Size::with_receptivity(size, group.receptivity),
+        ));
+
+        Ok(self.world.spawn(builder.build()))
+    }
+
+    pub fn locks_entry(&self) -> bool {
+        self.boss.as_ref().is_some_and(|boss| boss.locks_entry())
+    }
+
+    /// Updates the shared set of locked areas when the area's lock changed.
+    pub fn publish_entry_lock(&self) {
+        let locked = self.locks_entry();
+
+        if self.locked_areas.load().contains(&self.key) == locked {
+            return;
+        }
+
+        self.locked_areas.rcu(|areas| {
+            let mut areas = HashSet::clone(areas);
+
+            match locked {
+                true => areas.insert(self.key.clone()),
+                false => areas.remove(&self.key),
+            };
+
+            areas
         });
+    }
+
+    /// True when the area has no boss fight or its fight has been completed.
+    pub fn boss_completed(&self) -> bool {
+        self.boss.as_ref().is_none_or(|boss| boss.is_completed())
+    }
 
-        self.world.spawn_batch(enemies);
+    pub fn spawn_projectile(
+        &mut self,
+        owner: Entity,
+        pos: Vec2,
+        direction: Vec2,
+        spec: &ProjectileSpec,
+    ) -> Entity {
+        self.world.spawn((
+            Projectile { owner },
+            Lifetime(spec.lifetime),
+            Position(pos),
+            TargetPosition(pos),
+            Velocity(Vec2::ZERO),
+            Direction(direction),
+            Speed::new(spec.speed),
+            Size::new(spec.size),
+            spec.color.clone(),
+        ))
     }
 
-    pub fn spawn_player(&mut self) -> (Entity, u64) {
+    pub fn spawn_player(&mut self, class: &HeroClass) -> (Entity, u64) {
         let timer = Timer::new();
 
         let timestamp = timer.timestamp();
 
-        let entity = self.world.spawn((
+        let color = class
+            .color
+            .clone()
+            .unwrap_or_else(|| Color::rgb(self.rng.random(), self.rng.random(), self.rng.random()));
+
+        let mut builder = EntityBuilder::new();
+
+        builder.add_bundle((
             Position(self.spawn_pos),
             TargetPosition(self.spawn_pos),
             Velocity(Vec2::ZERO),
-            Speed(17.0),
+            Speed::new(class.speed),
             Direction(Vec2::ZERO),
-            Size(1.0),
-            Color::rgb(rand::random(), rand::random(), rand::random()),
-            Energy(0.0),
-            Regen(10.0),
-            MaxEnergy(100.0),
+            Size::new(class.size),
+            color,
             Hero,
             Bounded,
+            WallBounded,
             timer,
         ));
 
+        builder.add_bundle((
+            Energy(0.0),
+            Regen::new(class.regen),
+            MaxEnergy::new(class.max_energy),
+            Abilities::new(class.abilities.clone()),
+            Invulnerable::default(),
+            AbilityEffects::default(),
+            ActiveAuras::default(),
+            Momentum(Vec2::ZERO),
+            HeroClassId(class.id.clone()),
+            HeroProgress::default(),
+        ));
+
+        if let Some(inertia) = class.inertia {
+            builder.add(inertia);
+        }
+
+        let entity = self.world.spawn(builder.build());
+
         (entity, timestamp)
     }
 
+    /// Empty areas aren't closed here, the area actor closes once the grace period passes.
     pub fn despawn_player(
         &mut self,
         entity: Entity,
-    ) -> (Result<TakenEntity<'_>, hecs::NoSuchEntity>, bool) {
-        let hero_count = self.world.query_mut::<&Hero>().into_iter().count();
-        let should_close = hero_count == 1;
-
-        if should_close {
-            self.close();
-        }
-
-        (self.world.take(entity), should_close)
+    ) -> Result<TakenEntity<'_>, hecs::NoSuchEntity> {
+        self.world.take(entity)
     }
 
     pub fn update_player_input(&mut self, entity: Entity, input: Vec2) {
@@ -193,6 +450,15 @@ impl Area {
         }
     }
 
+    pub fn request_ability(&mut self, entity: Entity, slot: usize) {
+        let abilities = self.world.query_one_mut::<&mut Abilities>(entity);
+        if let Ok(abilities) = abilities
+            && let Some(slot) = abilities.0.get_mut(slot)
+        {
+            slot.requested = true;
+        }
+    }
+
     pub fn definition_packet(&self) -> Vec<u8> {
         let mut packet = Vec::new();
 
@@ -214,6 +480,7 @@ impl Area {
         for portal in &self.portals {
             packet.extend_from_slice(&portal.rect.to_bytes());
             packet.extend_from_slice(&portal.color.to_bytes());
+            packet.push(portal.is_locked() as u8);
         }
 
         let flags = self.flags.boss as u8
@@ -240,8 +507,67 @@ impl Area {
             packet.push(0);
         }
 
+        packet.extend_from_slice(&(self.dynamic_walls.len() as u16).to_le_bytes());
+        packet.extend_from_slice(&(self.switches.len() as u16).to_le_bytes());
+
+        for wall in &self.dynamic_walls {
+            packet.extend_from_slice(&wall.to_bytes());
+        }
+
+        for switch in &self.switches {
+            packet.extend_from_slice(&switch.shape.to_bytes());
+        }
+
+        packet.extend_from_slice(&(self.teleporters.len() as u16).to_le_bytes());
+
+        for teleporter in &self.teleporters {
+            packet.extend_from_slice(&teleporter.rect.to_bytes());
+            packet.extend_from_slice(&teleporter.color.to_bytes());
+        }
+
+        packet.extend_from_slice(&(self.force_zones.len() as u16).to_le_bytes());
+
+        for zone in &self.force_zones {
+            packet.extend_from_slice(&zone.shape.to_bytes());
+            packet.extend_from_slice(&zone.color.to_bytes());
+        }
+
+        packet.extend_from_slice(&(self.surface_zones.len() as u16).to_le_bytes());
+
+        for zone in &self.surface_zones {
+            packet.extend_from_slice(&zone.shape.to_bytes());
+            packet.extend_from_slice(&zone.color.to_bytes());
+        }
+
         packet
     }
+
+    /// Serializes the dynamic walls that changed since the last call, or `None` if nothing did.
+    pub fn take_wall_delta(&mut self) -> Option<Vec<u8>> {
+        let changed: Vec<(usize, &mut DynamicWall)> = self
+            .dynamic_walls
+            .iter_mut()
+            .enumerate()
+            .filter(|(_, wall)| wall.changed)
+            .collect();
+
+        if changed.is_empty() {
+            return None;
+        }
+
+        let mut delta = Vec::new();
+
+        delta.extend_from_slice(&(changed.len() as u16).to_le_bytes());
+
+        for (index, wall) in changed {
+            wall.changed = false;
+
+            delta.extend_from_slice(&(index as u16).to_le_bytes());
+            delta.extend_from_slice(&wall.to_bytes());
+        }
+
+        Some(delta)
+    }
 }
 #[derive(Eq, Clone)]
 pub struct AreaKey {
@@ -324,17 +650,28 @@ pub struct AreaTemplate {
 
     pub spawn_pos: Vec2,
 
-    pub inner_walls: Vec<Rect>,
-    pub safe_zones: Vec<Rect>,
+    pub inner_walls: Vec<Shape>,
+    pub dynamic_walls: Vec<DynamicWall>,
+    pub switches: Vec<PressureSwitch>,
+    pub force_zones: Vec<ForceZone>,
+    pub surface: Option<Inertia>,
+    pub surface_zones: Vec<SurfaceZone>,
+    pub safe_zones: Vec<Shape>,
     pub portals: Vec<Portal>,
+    pub teleporters: Vec<Teleporter>,
 
     pub enemy_groups: Vec<EnemyGroup>,
+    pub spawners: Vec<SpawnerConfig>,
 
     pub flags: AreaFlags,
+    pub boss: Option<BossConfig>,
+    pub death_timer: Option<f32>,
+
+    pub seed: Option<u64>,
 }
 
 impl AreaTemplate {
-    pub fn new(data: AreaData, order: u16, ctx: &AreaCreationContext) -> Self {
+    pub fn new(data: AreaData, order: u16, ctx: &AreaCreationContext) -> Result<Self> {
         let key = AreaKey::new(ctx.map_id.clone(), order as u16);
 
         let name = data.name.unwrap_or_else(|| format!("Area {}", order + 1));
@@ -362,32 +699,48 @@ impl AreaTemplate {
         let portal_ctx = PortalCreationContext {
             map_id: ctx.map_id.clone(),
             area_order: order,
+            map_exists: ctx.map_exists,
         };
 
         let portals = match data.portals {
             Some(portals) => portals
                 .into_iter()
                 .map(|data| Portal::new(data, &portal_ctx))
-                .collect::<Vec<_>>(),
+                .collect::<Result<Vec<_>>>()?,
             None => Vec::new(),
         };
 
-        let enemy_groups = data.enemy_groups.unwrap_or_default();
-        let enemy_groups = enemy_groups
+        let enemy_groups = data
+            .enemy_groups
+            .unwrap_or_default()
             .into_iter()
-            .map(|group| EnemyGroup {
-                color: group.color.into(),
-                count: group.count,
-                speed: group.speed,
-                size: group.size,
-            })
+            .map(EnemyGroup::from)
             .collect::<Vec<_>>();
 
+        let dynamic_walls = data
+            .dynamic_walls
+            .unwrap_or_default()
+            .into_iter()
+            .map(DynamicWall::new)
+            .collect::<Vec<_>>();
+
+        let switches = data
+            .switches
+            .unwrap_or_default()
+            .into_iter()
+            .map(|data| PressureSwitch::new(data, &dynamic_walls))
+            .collect();
+
+        let boss = data.boss.map(BossConfig::new);
+
+        let mut flags = AreaFlags::new(data.flags);
+        flags.boss |= boss.is_some();
+
         let message = data
             .message
             .map(|message| AreaMessage::new(message, data.message_config, data.vp));
 
-        AreaTemplate {
+        Ok(AreaTemplate {
             key,
             alias: data.alias,
             name: name.clone(),
@@ -406,18 +759,58 @@ impl AreaTemplate {
                 .spawn_pos
                 .unwrap_or_else(|| Vec2::new(5.0, height / 2.0)),
             portals,
-            inner_walls: data.inner_walls.unwrap_or_default(),
-            safe_zones: data.safe_zones.unwrap_or_default(),
+            teleporters: Teleporter::new_list(data.teleporters.unwrap_or_default()),
+            inner_walls: data
+                .inner_walls
+                .unwrap_or_default()
+                .into_iter()
+                .map(Shape::new)
+                .collect(),
+            dynamic_walls,
+            switches,
+            force_zones: data
+                .force_zones
+                .unwrap_or_default()
+                .into_iter()
+                .map(ForceZone::new)
+                .collect(),
+            surface: data.surface.map(Inertia::new),
+            surface_zones: data
+                .surface_zones
+                .unwrap_or_default()
+                .into_iter()
+                .map(SurfaceZone::new)
+                .collect(),
+            safe_zones: data
+                .safe_zones
+                .unwrap_or_default()
+                .into_iter()
+                .map(Shape::new)
+                .collect(),
             enemy_groups,
-            flags: AreaFlags::new(data.flags),
-        }
+            spawners: data
+                .spawners
+                .unwrap_or_default()
+                .into_iter()
+                .map(SpawnerConfig::new)
+                .collect(),
+            flags,
+            boss,
+            death_timer: data.death_timer.or(ctx.death_timer),
+            seed: data
+                .seed
+                .or_else(|| ctx.seed.map(|seed| seed.wrapping_add(order as u64))),
+        })
     }
 }
 
-pub struct AreaCreationContext {
+pub struct AreaCreationContext<'a> {
     pub map_id: String,
     pub map_name: String,
     pub background_color: String,
+    pub seed: Option<u64>,
+    pub death_timer: Option<f32>,
+    pub map_exists: &'a dyn Fn(&str) -> bool,
 }
 
 #[derive(Deserialize)]
@@ -437,13 +830,24 @@ pub struct AreaData {
 
     pub spawn_pos: Option<Vec2>,
 
-    pub inner_walls: Option<Vec<Rect>>,
-    pub safe_zones: Option<Vec<Rect>>,
+    pub inner_walls: Option<Vec<ShapeData>>,
+    pub dynamic_walls: Option<Vec<DynamicWallData>>,
+    pub switches: Option<Vec<PressureSwitchData>>,
+    pub force_zones: Option<Vec<ForceZoneData>>,
+    pub surface: Option<InertiaData>,
+    pub surface_zones: Option<Vec<SurfaceZoneData>>,
+    pub safe_zones: Option<Vec<ShapeData>>,
     pub portals: Option<Vec<PortalData>>,
+    pub teleporters: Option<Vec<TeleporterData>>,
 
     pub enemy_groups: Option<Vec<EnemyGroupData>>,
+    pub spawners: Option<Vec<SpawnerData>>,
 
     pub flags: Option<AreaFlagsData>,
+    pub boss: Option<BossData>,
+    pub death_timer: Option<f32>,
+
+    pub seed: Option<u64>,
 }
 
 #[derive(Clone)]
@@ -481,8 +885,16 @@ pub struct AreaFlagsData {
 pub struct EnemyGroup {
     pub color: Color,
     pub count: u32,
-    pub speed: f32,
-    pub size: f32,
+    pub speed: StatRange,
+    pub size: StatRange,
+    pub receptivity: f32,
+    pub direction: SpawnDirection,
+    /// Area-relative shapes enemies are spawned in. Empty to use the whole spawn area.
+    pub spawn_region: Vec<Shape>,
+    pub behavior: EnemyBehavior,
+    pub aura: Option<Aura>,
+    pub shooter: Option<ShooterConfig>,
+    pub flags: EnemyFlags,
 }
 
 impl EnemyGroup {
@@ -490,8 +902,45 @@ impl EnemyGroup {
         Self {
             color,
             count,
-            speed,
-            size,
+            speed: StatRange::fixed(speed),
+            size: StatRange::fixed(size),
+            receptivity: 1.0,
+            direction: SpawnDirection::Random,
+            spawn_region: Vec::new(),
+            behavior: EnemyBehavior::Bouncing,
+            aura: None,
+            shooter: None,
+            flags: EnemyFlags::new(None),
+        }
+    }
+}
+
+impl From<EnemyGroupData> for EnemyGroup {
+    fn from(data: EnemyGroupData) -> Self {
+        let color: Color = data.color.into();
+
+        Self {
+            count: data.count,
+            speed: StatRange::new(data.speed),
+            size: StatRange::new(data.size),
+            receptivity: data.receptivity.unwrap_or(1.0),
+            direction: data
+                .direction
+                .map(SpawnDirection::new)
+                .unwrap_or(SpawnDirection::Random),
+            spawn_region: data
+                .spawn_region
+                .unwrap_or_default()
+                .into_iter()
+                .map(Shape::new)
+                .collect(),
+            behavior: EnemyBehavior::new(data.behavior),
+            aura: data.aura.map(Aura::new),
+            shooter: data
+                .shooter
+                .map(|shooter| ShooterConfig::new(shooter, &color)),
+            flags: EnemyFlags::new(data.flags),
+            color,
         }
     }
 }
@@ -500,8 +949,52 @@ impl EnemyGroup {
 pub struct EnemyGroupData {
     pub color: String,
     pub count: u32,
-    pub speed: f32,
-    pub size: f32,
+    pub speed: StatRangeData,
+    pub size: StatRangeData,
+    pub receptivity: Option<f32>,
+    pub direction: Option<SpawnDirectionData>,
+    pub spawn_region: Option<Vec<ShapeData>>,
+    pub behavior: Option<EnemyBehaviorData>,
+    pub aura: Option<AuraData>,
+    pub shooter: Option<ShooterData>,
+    pub flags: Option<EnemyFlagsData>,
+}
+
+#[derive(Clone)]
+pub struct EnemyFlags {
+    pub pass_through_walls: bool,
+    pub enter_safe_zones: bool,
+    /// Wraps around the area edges instead of bouncing off them.
+    pub wrap_around: bool,
+    /// Decorative enemies that don't down heroes.
+    pub harmless: bool,
+}
+
+impl EnemyFlags {
+    pub fn new(data: Option<EnemyFlagsData>) -> Self {
+        match data {
+            Some(data) => Self {
+                pass_through_walls: data.pass_through_walls.unwrap_or(false),
+                enter_safe_zones: data.enter_safe_zones.unwrap_or(false),
+                wrap_around: data.wrap_around.unwrap_or(false),
+                harmless: data.harmless.unwrap_or(false),
+            },
+            None => Self {
+                pass_through_walls: false,
+                enter_safe_zones: false,
+                wrap_around: false,
+                harmless: false,
+            },
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct EnemyFlagsData {
+    pub pass_through_walls: Option<bool>,
+    pub enter_safe_zones: Option<bool>,
+    pub wrap_around: Option<bool>,
+    pub harmless: Option<bool>,
 }
 
 #[derive(Clone)]
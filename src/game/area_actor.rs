@@ -0,0 +1,546 @@
+use super::{
+    area::{Area, AreaKey},
+    aura::ActiveAuras,
+    components::{
+        CrossingPortal, DeathTimer, Downed, HeroProgress, Position, RefusedPortal, SpawnProtection,
+        Timer,
+    },
+    hero_class::HeroClass,
+    systems::*,
+    teleporter::TeleportCooldown,
+    timestep::{FixedTimestep, TimestepSettings},
+    transfer_request::TransferRequestTargetPos,
+};
+use crate::{
+    networking::{leaderboard::AreaInfo, rendering::AreaRenderMessage},
+    physics::vec2::Vec2,
+};
+use anyhow::Result;
+use hecs::{Entity, World};
+use std::time::Duration;
+use tokio::{
+    sync::{
+        mpsc::{self, error::SendError},
+        oneshot,
+    },
+    time::{Instant, MissedTickBehavior, interval},
+};
+
+/// How areas behave after their last hero leaves.
+#[derive(Clone, Copy)]
+pub struct HibernationSettings {
+    pub grace_period: Duration,
+    pub pause_when_empty: bool,
+}
+
+/// A hero removed from its area, kept in a world of its own while it moves to another area.
+pub struct TakenHero(Box<World>);
+
+/// Why an area could not receive a hero. Holds the hero if it never reached the area,
+/// so it can be put back where it came from.
+pub struct ReceiveError {
+    pub hero: Option<TakenHero>,
+    pub error: anyhow::Error,
+}
+
+pub struct SpawnedHero {
+    pub entity: Entity,
+    pub timestamp: u64,
+    pub area_info: AreaInfo,
+    pub definition: Vec<u8>,
+}
+
+pub struct ArrivedHero {
+    pub entity: Entity,
+    pub timer: Option<Timer>,
+    pub area_info: AreaInfo,
+    pub route_name: String,
+    pub definition: Vec<u8>,
+}
+
+pub enum AreaCommand {
+    Input {
+        entity: Entity,
+        input: Vec2,
+    },
+    Ability {
+        entity: Entity,
+        slot: usize,
+    },
+    Progress {
+        entity: Entity,
+        progress: HeroProgress,
+    },
+    Spawn {
+        class: &'static HeroClass,
+        reply: oneshot::Sender<SpawnedHero>,
+    },
+    Despawn {
+        entity: Entity,
+        reply: oneshot::Sender<Result<()>>,
+    },
+    ResetTimer {
+        entity: Entity,
+        reply: oneshot::Sender<Option<u64>>,
+    },
+    CancelCrossing {
+        entity: Entity,
+    },
+    Move {
+        entity: Entity,
+        target_pos: Option<TransferRequestTargetPos>,
+        reply: oneshot::Sender<Result<()>>,
+    },
+    Take {
+        entity: Entity,
+        reply: oneshot::Sender<Result<TakenHero>>,
+    },
+    Receive {
+        hero: TakenHero,
+        target_pos: Option<TransferRequestTargetPos>,
+        reply: oneshot::Sender<Result<ArrivedHero>>,
+    },
+    /// Stops the actor if the area is still empty. Replies whether it stopped.
+    CloseIfEmpty {
+        reply: oneshot::Sender<bool>,
+    },
+}
+
+/// Sends commands to the task that owns an area. Commands to an area that already closed
+/// are dropped, and requests waiting for a reply fail.
+#[derive(Clone)]
+pub struct AreaHandle {
+    tx: mpsc::UnboundedSender<AreaCommand>,
+}
+
+impl AreaHandle {
+    pub fn update_input(&self, entity: Entity, input: Vec2) {
+        let _ = self.tx.send(AreaCommand::Input { entity, input });
+    }
+
+    pub fn request_ability(&self, entity: Entity, slot: usize) {
+        let _ = self.tx.send(AreaCommand::Ability { entity, slot });
+    }
+
+    pub fn update_progress(&self, entity: Entity, progress: HeroProgress) {
+        let _ = self.tx.send(AreaCommand::Progress { entity, progress });
+    }
+
+    pub fn cancel_crossing(&self, entity: Entity) {
+        let _ = self.tx.send(AreaCommand::CancelCrossing { entity });
+    }
+
+    pub async fn spawn_player(&self, class: &'static HeroClass) -> Result<SpawnedHero> {
+        self.request(|reply| AreaCommand::Spawn { class, reply })
+            .await
+    }
+
+    pub async fn despawn_player(&self, entity: Entity) -> Result<()> {
+        self.request(|reply| AreaCommand::Despawn { entity, reply })
+            .await?
+    }
+
+    pub async fn reset_timer(&self, entity: Entity) -> Result<Option<u64>> {
+        self.request(|reply| AreaCommand::ResetTimer { entity, reply })
+            .await
+    }
+
+    pub async fn move_hero(
+        &self,
+        entity: Entity,
+        target_pos: Option<TransferRequestTargetPos>,
+    ) -> Result<()> {
+        self.request(|reply| AreaCommand::Move {
+            entity,
+            target_pos,
+            reply,
+        })
+        .await?
+    }
+
+    pub async fn take_hero(&self, entity: Entity) -> Result<TakenHero> {
+        self.request(|reply| AreaCommand::Take { entity, reply })
+            .await?
+    }
+
+    pub async fn receive_hero(
+        &self,
+        hero: TakenHero,
+        target_pos: Option<TransferRequestTargetPos>,
+    ) -> Result<ArrivedHero, ReceiveError> {
+        let (reply, result) = oneshot::channel();
+
+        let command = AreaCommand::Receive {
+            hero,
+            target_pos,
+            reply,
+        };
+
+        if let Err(SendError(AreaCommand::Receive { hero, .. })) = self.tx.send(command) {
+            return Err(ReceiveError {
+                hero: Some(hero),
+                error: anyhow::anyhow!("Area is closed"),
+            });
+        }
+
+        match result.await {
+            Ok(arrived) => arrived.map_err(|error| ReceiveError { hero: None, error }),
+            Err(err) => Err(ReceiveError {
+                hero: None,
+                error: err.into(),
+            }),
+        }
+    }
+
+    pub async fn close_if_empty(&self) -> bool {
+        self.request(|reply| AreaCommand::CloseIfEmpty { reply })
+            .await
+            .unwrap_or(true)
+    }
+
+    async fn request<T>(
+        &self,
+        command: impl FnOnce(oneshot::Sender<T>) -> AreaCommand,
+    ) -> Result<T> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+
+        self.tx
+            .send(command(reply_tx))
+            .map_err(|_| anyhow::anyhow!("Area is closed"))?;
+
+        Ok(reply_rx.await?)
+    }
+}
+
+/// Owns an area and runs its simulation on its own task, so areas never wait on each other.
+pub struct AreaActor {
+    area: Area,
+    rx: mpsc::UnboundedReceiver<AreaCommand>,
+    timestep: TimestepSettings,
+    hibernation: HibernationSettings,
+    /// Seconds heroes can't be downed for after spawning or arriving somewhere.
+    spawn_protection: f32,
+    close_tx: mpsc::UnboundedSender<AreaKey>,
+}
+
+impl AreaActor {
+    pub fn spawn(
+        area: Area,
+        timestep: TimestepSettings,
+        hibernation: HibernationSettings,
+        spawn_protection: f32,
+        close_tx: mpsc::UnboundedSender<AreaKey>,
+    ) -> AreaHandle {
+        let (tx, rx) = mpsc::unbounded_channel();
+
+        let actor = Self {
+            area,
+            rx,
+            timestep,
+            hibernation,
+            spawn_protection,
+            close_tx,
+        };
+
+        tokio::spawn(actor.run());
+
+        AreaHandle { tx }
+    }
+
+    async fn run(mut self) {
+        let mut timestep = FixedTimestep::new(self.timestep);
+        let delta_time = timestep.step().as_secs_f32();
+
+        let mut last_time = Instant::now();
+
+        let mut empty_for = Duration::ZERO;
+        let mut close_requested = false;
+
+        let mut interval = interval(self.timestep.step);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+        loop {
+            tokio::select! {
+                command = self.rx.recv() => {
+                    let Some(command) = command else {
+                        break;
+                    };
+
+                    if self.handle_command(command) {
+                        break;
+                    }
+                }
+                _ = interval.tick() => {
+                    let now = Instant::now();
+                    let elapsed = now - last_time;
+                    let steps = timestep.advance(elapsed);
+                    last_time = now;
+
+                    let empty = self.area.hero_count() == 0;
+
+                    if empty {
+                        empty_for += elapsed;
+
+                        if empty_for >= self.hibernation.grace_period && !close_requested {
+                            close_requested = self.close_tx.send(self.area.key.clone()).is_ok();
+                        }
+                    } else {
+                        empty_for = Duration::ZERO;
+                        close_requested = false;
+                    }
+
+                    if empty && self.hibernation.pause_when_empty {
+                        continue;
+                    }
+
+                    for _ in 0..steps {
+                        update_area(&mut self.area, delta_time).await;
+                    }
+
+                    if timestep.should_render() {
+                        self.render().await;
+                    }
+                }
+            }
+        }
+    }
+
+    async fn render(&mut self) {
+        system_render(&mut self.area);
+
+        let wall_delta = self.area.take_wall_delta();
+
+        if let Some(packet) = &self.area.render_packet {
+            let _ = self
+                .area
+                .render_tx
+                .send(AreaRenderMessage {
+                    key: self.area.key.clone(),
+                    packet: packet.clone(),
+                    wall_delta,
+                })
+                .await;
+        }
+    }
+
+    /// Returns true when the actor should stop.
+    fn handle_command(&mut self, command: AreaCommand) -> bool {
+        match command {
+            AreaCommand::Input { entity, input } => self.area.update_player_input(entity, input),
+            AreaCommand::Ability { entity, slot } => self.area.request_ability(entity, slot),
+            AreaCommand::Progress { entity, progress } => {
+                if let Ok(current) = self.area.world.query_one_mut::<&mut HeroProgress>(entity) {
+                    *current = progress;
+                }
+            }
+            AreaCommand::Spawn { class, reply } => {
+                let (entity, timestamp) = self.area.spawn_player(class);
+                self.protect(entity);
+
+                let _ = reply.send(SpawnedHero {
+                    entity,
+                    timestamp,
+                    area_info: AreaInfo::from_area(&self.area),
+                    definition: self.area.definition_packet(),
+                });
+            }
+            AreaCommand::Despawn { entity, reply } => {
+                let result = self.area.despawn_player(entity).map(|_| ());
+                let _ = reply.send(result.map_err(Into::into));
+            }
+            AreaCommand::ResetTimer { entity, reply } => {
+                let _ = reply.send(self.reset_timer(entity));
+            }
+            AreaCommand::CancelCrossing { entity } => {
+                let _ = self.area.world.remove_one::<CrossingPortal>(entity);
+            }
+            AreaCommand::Move {
+                entity,
+                target_pos,
+                reply,
+            } => {
+                let _ = reply.send(self.move_hero(entity, target_pos));
+            }
+            AreaCommand::Take { entity, reply } => {
+                let _ = reply.send(self.take_hero(entity));
+            }
+            AreaCommand::Receive {
+                hero,
+                target_pos,
+                reply,
+            } => {
+                let _ = reply.send(self.receive_hero(hero, target_pos));
+            }
+            AreaCommand::CloseIfEmpty { reply } => {
+                let empty = self.area.hero_count() == 0;
+                let _ = reply.send(empty);
+
+                return empty;
+            }
+        }
+
+        false
+    }
+
+    fn reset_timer(&mut self, entity: Entity) -> Option<u64> {
+        let _ = self.area.world.remove::<(Downed, DeathTimer)>(entity);
+
+        if let Ok(progress) = self.area.world.query_one_mut::<&mut HeroProgress>(entity) {
+            *progress = HeroProgress::default();
+        }
+
+        let timer = self.area.world.query_one_mut::<&mut Timer>(entity).ok()?;
+        timer.reset();
+
+        Some(timer.timestamp())
+    }
+
+    fn protect(&mut self, entity: Entity) {
+        if self.spawn_protection > 0.0 {
+            let _ = self
+                .area
+                .world
+                .insert_one(entity, SpawnProtection(self.spawn_protection));
+        }
+    }
+
+    fn resolve_target_pos(&self, target_pos: Option<TransferRequestTargetPos>) -> Vec2 {
+        match target_pos {
+            Some(target_pos) => Vec2::new(
+                target_pos.x.resolve(&self.area.bounds),
+                target_pos.y.resolve(&self.area.bounds),
+            ),
+            None => self.area.spawn_pos,
+        }
+    }
+
+    fn move_hero(
+        &mut self,
+        entity: Entity,
+        target_pos: Option<TransferRequestTargetPos>,
+    ) -> Result<()> {
+        let target_pos = self.resolve_target_pos(target_pos);
+
+        let pos = self.area.world.query_one_mut::<&mut Position>(entity)?;
+        pos.0 = target_pos;
+
+        let _ = self.area.world.remove_one::<CrossingPortal>(entity);
+        self.protect(entity);
+
+        Ok(())
+    }
+
+    fn take_hero(&mut self, entity: Entity) -> Result<TakenHero> {
+        let taken = self.area.despawn_player(entity)?;
+
+        let mut world = World::new();
+        world.spawn(taken);
+
+        Ok(TakenHero(Box::new(world)))
+    }
+
+    fn receive_hero(
+        &mut self,
+        hero: TakenHero,
+        target_pos: Option<TransferRequestTargetPos>,
+    ) -> Result<ArrivedHero> {
+        let mut world = *hero.0;
+
+        let entity = world
+            .iter()
+            .next()
+            .map(|hero| hero.entity())
+            .ok_or_else(|| anyhow::anyhow!("Transferred hero is missing"))?;
+
+        let entity = self.area.world.spawn(world.take(entity)?);
+
+        let _ = self.area.world.remove_one::<CrossingPortal>(entity);
+        let _ = self.area.world.remove_one::<TeleportCooldown>(entity);
+        let _ = self.area.world.remove_one::<RefusedPortal>(entity);
+
+        // aura sources are entities of the old area, dropping the effects ends them
+        if let Ok(auras) = self.area.world.query_one_mut::<&mut ActiveAuras>(entity) {
+            auras.0.clear();
+        }
+
+        self.protect(entity);
+
+        let target_pos = self.resolve_target_pos(target_pos);
+
+        let world = &mut self.area.world;
+        let timer = world.query_one_mut::<&mut Timer>(entity).ok().cloned();
+
+        if let Ok(pos) = world.query_one_mut::<&mut Position>(entity) {
+            pos.0 = target_pos;
+        }
+
+        let route_name = match &self.area.route_name {
+            Some(route) => route,
+            None => match &self.area.flags.final_victory {
+                true => &self.area.map_name,
+                false => &self.area.full_name,
+            },
+        }
+        .clone();
+
+        Ok(ArrivedHero {
+            entity,
+            timer,
+            area_info: AreaInfo::from_area(&self.area),
+            route_name,
+            definition: self.area.definition_packet(),
+        })
+    }
+}
+
+async fn update_area(area: &mut Area, delta_time: f32) {
+    area.frame_count += 1;
+    area.time += delta_time;
+    area.delta_time = delta_time;
+
+    system_update_energy(area);
+    system_abilities(area).await;
+
+    system_homing(area);
+    system_wall_hugging(area);
+
+    system_update_velocity(area);
+
+    system_wavy(area);
+    system_dasher(area);
+    system_stop_and_go(area);
+    system_orbit(area);
+
+    system_shooters(area);
+    system_boss(area);
+    area.publish_entry_lock();
+    system_spawners(area);
+
+    system_force_zones(area);
+
+    system_dynamic_walls(area);
+
+    system_evaluate_target_position(area);
+
+    system_bounds_check(area);
+    system_wrap_around(area);
+    system_inner_wall_collision(area);
+    system_safe_zone_collision(area);
+
+    system_commit_position(area);
+
+    system_teleporters(area);
+    system_portals(area).await;
+
+    system_update_spatial_hash(area);
+
+    system_projectiles(area).await;
+    system_lifetime(area);
+    system_spawn_protection(area);
+
+    system_hero_collision(area).await;
+    system_enemy_collision(area).await;
+    system_death_timer(area).await;
+
+    system_auras(area);
+}
@@ -0,0 +1,96 @@
+use super::components::Color;
+use crate::{effects::Effect, value::Value};
+use hecs::Entity;
+use serde::Deserialize;
+
+#[derive(Clone, Copy)]
+pub enum AuraEffect {
+    Slow { multiplier: f32 },
+    Freeze { multiplier: f32 },
+    EnergyDrain { rate: f32 },
+    Enlarge { multiplier: f32 },
+}
+
+impl AuraEffect {
+    pub fn new(data: AuraEffectData) -> Self {
+        match data {
+            AuraEffectData::Slow { multiplier } => Self::Slow {
+                multiplier: multiplier.unwrap_or(0.7),
+            },
+            AuraEffectData::Freeze { multiplier } => Self::Freeze {
+                multiplier: multiplier.unwrap_or(0.15),
+            },
+            AuraEffectData::EnergyDrain { rate } => Self::EnergyDrain {
+                rate: rate.unwrap_or(15.0),
+            },
+            AuraEffectData::Enlarge { multiplier } => Self::Enlarge {
+                multiplier: multiplier.unwrap_or(1.5),
+            },
+        }
+    }
+
+    /// Auras sharing an id don't stack, so standing in two slowing auras only slows once.
+    pub fn id(&self) -> &'static str {
+        match self {
+            Self::Slow { .. } => "slowing_aura",
+            Self::Freeze { .. } => "freezing_aura",
+            Self::EnergyDrain { .. } => "draining_aura",
+            Self::Enlarge { .. } => "enlarging_aura",
+        }
+    }
+
+    fn default_color(&self) -> Color {
+        match self {
+            Self::Slow { .. } => Color::rgba(255, 0, 0, 40),
+            Self::Freeze { .. } => Color::rgba(0, 200, 255, 40),
+            Self::EnergyDrain { .. } => Color::rgba(128, 0, 255, 40),
+            Self::Enlarge { .. } => Color::rgba(255, 128, 0, 40),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Aura {
+    pub radius: f32,
+    pub effect: AuraEffect,
+    pub color: Color,
+}
+
+impl Aura {
+    pub fn new(data: AuraData) -> Self {
+        let effect = AuraEffect::new(data.effect);
+
+        Self {
+            radius: data.radius,
+            color: data
+                .color
+                .map(Color::from)
+                .unwrap_or_else(|| effect.default_color()),
+            effect,
+        }
+    }
+}
+
+pub struct ActiveAura {
+    pub source: Entity,
+    pub effect: Effect<Value<f32>>,
+}
+
+/// Aura effects currently applied to a hero, removed as soon as the hero leaves the aura.
+#[derive(Default)]
+pub struct ActiveAuras(pub Vec<ActiveAura>);
+
+#[derive(Deserialize)]
+pub struct AuraData {
+    pub radius: f32,
+    pub effect: AuraEffectData,
+    pub color: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub enum AuraEffectData {
+    Slow { multiplier: Option<f32> },
+    Freeze { multiplier: Option<f32> },
+    EnergyDrain { rate: Option<f32> },
+    Enlarge { multiplier: Option<f32> },
+}
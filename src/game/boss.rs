@@ -0,0 +1,131 @@
+use super::{
+    area::{EnemyGroup, EnemyGroupData},
+    enemy_behavior::{EnemyBehavior, EnemyBehaviorData},
+};
+use serde::Deserialize;
+
+#[derive(Clone, Copy, Deserialize)]
+pub enum BossTrigger {
+    /// Seconds since the fight started.
+    Time(f32),
+    /// Furthest hero position along the area, from 0 at the left edge to 1 at the right edge.
+    Progress(f32),
+}
+
+impl BossTrigger {
+    pub fn is_met(&self, elapsed: f32, progress: f32) -> bool {
+        match *self {
+            Self::Time(time) => elapsed >= time,
+            Self::Progress(target) => progress >= target,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct BossPhase {
+    pub trigger: BossTrigger,
+    pub speed: Option<f32>,
+    pub size: Option<f32>,
+    pub behavior: Option<EnemyBehavior>,
+}
+
+impl BossPhase {
+    pub fn new(data: BossPhaseData) -> Self {
+        Self {
+            trigger: data.trigger,
+            speed: data.speed,
+            size: data.size,
+            behavior: data
+                .behavior
+                .map(|behavior| EnemyBehavior::new(Some(behavior))),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct BossEnemyGroup {
+    pub group: EnemyGroup,
+    pub phases: Vec<BossPhase>,
+}
+
+#[derive(Clone)]
+pub struct BossConfig {
+    pub enemies: Vec<BossEnemyGroup>,
+    pub completion: BossTrigger,
+    pub lock_entry: bool,
+}
+
+impl BossConfig {
+    pub fn new(data: BossData) -> Self {
+        Self {
+            enemies: data
+                .enemies
+                .into_iter()
+                .map(|enemy| BossEnemyGroup {
+                    group: enemy.group.into(),
+                    phases: enemy
+                        .phases
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(BossPhase::new)
+                        .collect(),
+                })
+                .collect(),
+            completion: data.completion,
+            lock_entry: data.lock_entry.unwrap_or(true),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum BossState {
+    /// No hero has stepped out of the safe zones yet.
+    Waiting,
+    Active {
+        elapsed: f32,
+    },
+    Completed,
+}
+
+pub struct BossFight {
+    pub config: BossConfig,
+    pub state: BossState,
+}
+
+impl BossFight {
+    pub fn new(config: BossConfig) -> Self {
+        Self {
+            config,
+            state: BossState::Waiting,
+        }
+    }
+
+    pub fn locks_entry(&self) -> bool {
+        self.config.lock_entry && matches!(self.state, BossState::Active { .. })
+    }
+
+    pub fn is_completed(&self) -> bool {
+        self.state == BossState::Completed
+    }
+}
+
+#[derive(Deserialize)]
+pub struct BossData {
+    pub enemies: Vec<BossEnemyData>,
+    pub completion: BossTrigger,
+    pub lock_entry: Option<bool>,
+}
+
+#[derive(Deserialize)]
+pub struct BossEnemyData {
+    pub group: EnemyGroupData,
+    pub phases: Option<Vec<BossPhaseData>>,
+}
+
+#[derive(Deserialize)]
+pub struct BossPhaseData {
+    pub trigger: BossTrigger,
+    pub speed: Option<f32>,
+    pub size: Option<f32>,
+    pub behavior: Option<EnemyBehaviorData>,
+}
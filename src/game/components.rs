@@ -1,6 +1,13 @@
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
-use crate::physics::vec2::Vec2;
+use super::{
+    abilities::{Ability, AbilitySlot},
+    area::AreaKey,
+    boss::BossPhase,
+    projectile::ShooterConfig,
+};
+use crate::{effects::core_types::bool_ops::BoolOps, physics::vec2::Vec2, value::Value};
+use hecs::Entity;
 use serde::Deserialize;
 
 #[derive(Clone)]
@@ -44,31 +51,172 @@ pub struct Hero;
 pub struct Enemy;
 
 pub struct Energy(pub f32);
-pub struct Regen(pub f32);
-pub struct MaxEnergy(pub f32);
+pub struct Regen(pub Value<f32>);
+
+impl Regen {
+    pub fn new(base: f32) -> Self {
+        Self(Value::new(base, 1.0))
+    }
+}
+
+pub struct MaxEnergy(pub Value<f32>);
+
+impl MaxEnergy {
+    pub fn new(base: f32) -> Self {
+        Self(Value::new(base, 1.0))
+    }
+}
 
 pub struct Downed;
 
+/// Seconds left until a downed hero is sent back to the start of the map.
+pub struct DeathTimer(pub f32);
+
+/// Seconds left before a hero that just spawned or arrived in an area can be downed.
+pub struct SpawnProtection(pub f32);
+
+pub struct Invulnerable(pub Value<bool, BoolOps, BoolOps>);
+
+impl Default for Invulnerable {
+    fn default() -> Self {
+        Self(Value::new(false, 1.0))
+    }
+}
+
+pub struct Abilities(pub Vec<AbilitySlot>);
+
+impl Abilities {
+    pub fn new(abilities: Vec<Ability>) -> Self {
+        Self(abilities.into_iter().map(AbilitySlot::new).collect())
+    }
+}
+
 pub struct Bounded;
 pub struct BounceOffBounds;
+pub struct WallBounded;
 pub struct SafeZoneBounded;
 
+/// Leaves the area on one edge and comes back in on the opposite one, instead of being bounded.
+pub struct WrapAround;
+
+/// Enemy that doesn't down heroes it touches.
+pub struct Harmless;
+
 pub struct CrossingPortal;
 
+/// Portal that refused the hero, so the reason is only sent once while it keeps touching it.
+pub struct RefusedPortal(pub usize);
+
+/// Id of the class the hero was spawned as.
+pub struct HeroClassId(pub String);
+
+/// Run progress of the hero's user, mirrored from the user registry for portal conditions.
+#[derive(Default)]
+pub struct HeroProgress {
+    pub vp: u32,
+    pub victories: Vec<AreaKey>,
+}
+
 pub struct Position(pub Vec2);
 pub struct TargetPosition(pub Vec2);
 
 pub struct Velocity(pub Vec2);
 
-pub struct Speed(pub f32);
+pub struct Speed(pub Value<f32>);
+
+impl Speed {
+    pub fn new(base: f32) -> Self {
+        Self::with_receptivity(base, 1.0)
+    }
+
+    pub fn with_receptivity(base: f32, receptivity: f32) -> Self {
+        Self(Value::scaled(base, receptivity))
+    }
+}
+
 pub struct Direction(pub Vec2);
 
-pub struct Size(pub f32);
+pub struct Size(pub Value<f32>);
 
 impl Size {
-    pub fn radius(&self) -> f32 {
-        self.0 / 2.0
+    pub fn new(base: f32) -> Self {
+        Self::with_receptivity(base, 1.0)
+    }
+
+    pub fn with_receptivity(base: f32, receptivity: f32) -> Self {
+        Self(Value::scaled(base, receptivity))
     }
+
+    pub fn radius(&mut self) -> f32 {
+        self.0.get() / 2.0
+    }
+}
+
+pub struct Homing {
+    pub range: f32,
+    pub turn_speed: f32,
+}
+
+pub struct Wavy {
+    pub amplitude: f32,
+    pub frequency: f32,
+    pub phase: f32,
+}
+
+pub struct Dasher {
+    pub dash_time: f32,
+    pub rest_time: f32,
+    pub dash_multiplier: f32,
+    pub range: f32,
+    pub timer: f32,
+    pub dashing: bool,
+}
+
+pub struct StopAndGo {
+    pub move_time: f32,
+    pub stop_time: f32,
+    pub timer: f32,
+    pub moving: bool,
+}
+
+pub struct Orbit {
+    pub center: Vec2,
+    pub radius: f32,
+    pub angle: f32,
+    pub clockwise: bool,
+}
+
+pub struct WallHugging {
+    pub clockwise: bool,
+}
+
+pub struct Shooter {
+    pub config: ShooterConfig,
+    pub timer: f32,
+    pub angle: f32,
+}
+
+pub struct Projectile {
+    pub owner: Entity,
+}
+
+/// Movement velocity of a hero, kept between ticks so inertia can ease it towards the input.
+pub struct Momentum(pub Vec2);
+
+/// Velocity built up from force zones, added on top of the entity's own movement.
+pub struct ExternalVelocity(pub Vec2);
+
+/// Seconds left before the entity is despawned.
+pub struct Lifetime(pub f32);
+
+/// Marks an enemy spawned by the spawner at this index in the area's spawner list.
+pub struct Spawned {
+    pub spawner: usize,
+}
+
+pub struct Boss {
+    pub phases: Vec<BossPhase>,
+    pub next_phase: usize,
 }
 
 #[derive(Clone, Default, Deserialize)]
@@ -0,0 +1,216 @@
+use crate::{
+    logger::Logger,
+    physics::{
+        shape::{Shape, ShapeData},
+        vec2::Vec2,
+    },
+};
+use serde::Deserialize;
+
+/// Moves a wall through a loop of offsets relative to where its shape is defined.
+#[derive(Clone)]
+pub struct WallPath {
+    pub points: Vec<Vec2>,
+    pub speed: f32,
+    pub target: usize,
+}
+
+/// Opens and closes a door on a fixed timer.
+#[derive(Clone)]
+pub struct DoorCycle {
+    pub open_duration: f32,
+    pub closed_duration: f32,
+    pub timer: f32,
+    pub open: bool,
+}
+
+/// Wall that can move or open during the simulation, unlike the static inner walls.
+#[derive(Clone)]
+pub struct DynamicWall {
+    pub id: Option<String>,
+    base: Shape,
+    pub shape: Shape,
+    pub offset: Vec2,
+    pub path: Option<WallPath>,
+    pub cycle: Option<DoorCycle>,
+    /// True while a hero stands on a pressure switch linked to this wall.
+    pub pressed: bool,
+    /// Set whenever the wall moves or opens, so clients can be sent only what changed.
+    pub changed: bool,
+}
+
+impl DynamicWall {
+    pub fn new(data: DynamicWallData) -> Self {
+        let base = Shape::new(data.shape);
+
+        let path = data
+            .path
+            .filter(|path| !path.points.is_empty())
+            .map(|path| WallPath {
+                points: path.points,
+                speed: path.speed,
+                target: 0,
+            });
+
+        let offset = path.as_ref().map_or(Vec2::ZERO, |path| path.points[0]);
+
+        let cycle = data.door.map(|door| {
+            let open = door.start_open.unwrap_or(false);
+
+            DoorCycle {
+                open_duration: door.open,
+                closed_duration: door.closed,
+                timer: if open { door.open } else { door.closed },
+                open,
+            }
+        });
+
+        Self {
+            id: data.id,
+            shape: base.translated(offset),
+            base,
+            offset,
+            path,
+            cycle,
+            pressed: false,
+            changed: false,
+        }
+    }
+
+    /// Pressing a switch toggles the door, so a door that is open on its timer closes
+    /// while the switch is held.
+    pub fn is_open(&self) -> bool {
+        self.cycle.as_ref().is_some_and(|cycle| cycle.open) != self.pressed
+    }
+
+    pub fn is_solid(&self) -> bool {
+        !self.is_open()
+    }
+
+    pub fn update(&mut self, delta_time: f32, pressed: bool) {
+        let was_open = self.is_open();
+
+        self.pressed = pressed;
+
+        if let Some(cycle) = &mut self.cycle {
+            cycle.timer -= delta_time;
+
+            if cycle.timer <= 0.0 {
+                cycle.open = !cycle.open;
+                cycle.timer += match cycle.open {
+                    true => cycle.open_duration,
+                    false => cycle.closed_duration,
+                };
+            }
+        }
+
+        if let Some(path) = &mut self.path {
+            let previous = self.offset;
+            let mut distance = path.speed * delta_time;
+
+            // bounded so a path of identical points can't loop forever
+            for _ in 0..path.points.len() {
+                let to_target = path.points[path.target] - self.offset;
+                let remaining = to_target.magnitude();
+
+                if remaining > distance {
+                    self.offset += to_target / remaining * distance;
+                    break;
+                }
+
+                self.offset = path.points[path.target];
+                path.target = (path.target + 1) % path.points.len();
+                distance -= remaining;
+            }
+
+            if self.offset != previous {
+                self.shape = self.base.translated(self.offset);
+                self.changed = true;
+            }
+        }
+
+        if self.is_open() != was_open {
+            self.changed = true;
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+
+        bytes.push(self.is_open() as u8);
+        bytes.extend_from_slice(&self.shape.to_bytes());
+
+        bytes
+    }
+}
+
+/// Plate that toggles its linked doors while a hero stands on it.
+#[derive(Clone)]
+pub struct PressureSwitch {
+    pub shape: Shape,
+    /// Indices into the area's dynamic wall list.
+    pub doors: Vec<usize>,
+}
+
+impl PressureSwitch {
+    pub fn new(data: PressureSwitchData, walls: &[DynamicWall]) -> Self {
+        let doors = data
+            .doors
+            .iter()
+            .filter_map(|id| {
+                let index = walls.iter().position(|wall| wall.id.as_ref() == Some(id));
+
+                if index.is_none() {
+                    Logger::warn(format!("Pressure switch references unknown door '{id}'"));
+                }
+
+                index
+            })
+            .collect();
+
+        Self {
+            shape: Shape::new(data.shape),
+            doors,
+        }
+    }
+}
+
+/// Static inner walls together with the dynamic walls that are currently closed.
+pub fn solid_walls<'a>(
+    inner_walls: &'a [Shape],
+    dynamic_walls: &'a [DynamicWall],
+) -> impl Iterator<Item = &'a Shape> {
+    inner_walls.iter().chain(
+        dynamic_walls
+            .iter()
+            .filter(|wall| wall.is_solid())
+            .map(|wall| &wall.shape),
+    )
+}
+
+#[derive(Deserialize)]
+pub struct DynamicWallData {
+    pub id: Option<String>,
+    pub shape: ShapeData,
+    pub path: Option<WallPathData>,
+    pub door: Option<DoorCycleData>,
+}
+
+#[derive(Deserialize)]
+pub struct WallPathData {
+    pub points: Vec<Vec2>,
+    pub speed: f32,
+}
+
+#[derive(Deserialize)]
+pub struct DoorCycleData {
+    pub open: f32,
+    pub closed: f32,
+    pub start_open: Option<bool>,
+}
+
+#[derive(Deserialize)]
+pub struct PressureSwitchData {
+    pub shape: ShapeData,
+    pub doors: Vec<String>,
+}
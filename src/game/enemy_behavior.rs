@@ -0,0 +1,288 @@
+use super::{
+    components::{BounceOffBounds, Dasher, Direction, Homing, Orbit, StopAndGo, WallHugging, Wavy},
+    enemy_spawn::MAX_SPAWN_ATTEMPTS,
+};
+use crate::physics::{rect::Rect, vec2::Vec2};
+use hecs::{Entity, EntityBuilder, World};
+use rand::Rng;
+use serde::Deserialize;
+use std::f32::consts::TAU;
+
+#[derive(Clone)]
+pub enum EnemyBehavior {
+    Bouncing,
+    Homing {
+        range: f32,
+        turn_speed: f32,
+    },
+    Wavy {
+        amplitude: f32,
+        frequency: f32,
+    },
+    Dasher {
+        dash_time: f32,
+        rest_time: f32,
+        dash_multiplier: f32,
+        range: f32,
+    },
+    StopAndGo {
+        move_time: f32,
+        stop_time: f32,
+    },
+    Orbit {
+        radius: f32,
+        clockwise: bool,
+    },
+    WallHugging {
+        clockwise: bool,
+    },
+}
+
+impl EnemyBehavior {
+    pub fn new(data: Option<EnemyBehaviorData>) -> Self {
+        let Some(data) = data else {
+            return Self::Bouncing;
+        };
+
+        match data {
+            EnemyBehaviorData::Bouncing => Self::Bouncing,
+            EnemyBehaviorData::Homing { range, turn_speed } => Self::Homing {
+                range: range.unwrap_or(10.0),
+                turn_speed: turn_speed.unwrap_or(180.0).to_radians(),
+            },
+            EnemyBehaviorData::Wavy {
+                amplitude,
+                frequency,
+            } => Self::Wavy {
+                amplitude: amplitude.unwrap_or(45.0).to_radians(),
+                frequency: frequency.unwrap_or(1.0),
+            },
+            EnemyBehaviorData::Dasher {
+                dash_time,
+                rest_time,
+                dash_multiplier,
+                range,
+            } => Self::Dasher {
+                dash_time: dash_time.unwrap_or(0.5),
+                rest_time: rest_time.unwrap_or(1.5),
+                dash_multiplier: dash_multiplier.unwrap_or(3.0),
+                range: range.unwrap_or(15.0),
+            },
+            EnemyBehaviorData::StopAndGo {
+                move_time,
+                stop_time,
+            } => Self::StopAndGo {
+                move_time: move_time.unwrap_or(1.0),
+                stop_time: stop_time.unwrap_or(1.0),
+            },
+            EnemyBehaviorData::Orbit { radius, clockwise } => Self::Orbit {
+                radius: radius.unwrap_or(3.0),
+                clockwise: clockwise.unwrap_or(true),
+            },
+            EnemyBehaviorData::WallHugging { clockwise } => Self::WallHugging {
+                clockwise: clockwise.unwrap_or(true),
+            },
+        }
+    }
+
+    pub fn bounces_off_bounds(&self) -> bool {
+        !matches!(self, Self::Orbit { .. } | Self::WallHugging { .. })
+    }
+
+    /// Adds the behavior's components to an enemy and returns the position it should spawn at.
+    /// `is_free` tells whether a position other than the sampled one is clear of obstacles.
+    pub fn add_components(
+        &self,
+        builder: &mut EntityBuilder,
+        sampled_pos: Vec2,
+        radius: f32,
+        bounds: &Rect,
+        is_free: impl Fn(Vec2) -> bool,
+        rng: &mut impl Rng,
+    ) -> Vec2 {
+        match *self {
+            Self::Bouncing => sampled_pos,
+            Self::Homing { range, turn_speed } => {
+                builder.add(Homing { range, turn_speed });
+                sampled_pos
+            }
+            Self::Wavy {
+                amplitude,
+                frequency,
+            } => {
+                builder.add(Wavy {
+                    amplitude,
+                    frequency,
+                    phase: rng.random::<f32>() * TAU,
+                });
+                sampled_pos
+            }
+            Self::Dasher {
+                dash_time,
+                rest_time,
+                dash_multiplier,
+                range,
+            } => {
+                builder.add(Dasher {
+                    dash_time,
+                    rest_time,
+                    dash_multiplier,
+                    range,
+                    timer: rng.random::<f32>() * rest_time,
+                    dashing: false,
+                });
+                sampled_pos
+            }
+            Self::StopAndGo {
+                move_time,
+                stop_time,
+            } => {
+                builder.add(StopAndGo {
+                    move_time,
+                    stop_time,
+                    timer: rng.random::<f32>() * move_time,
+                    moving: true,
+                });
+                sampled_pos
+            }
+            Self::Orbit {
+                radius: orbit_radius,
+                clockwise,
+            } => {
+                let center = orbit_center(sampled_pos, orbit_radius + radius, bounds);
+
+                let mut angle = rng.random::<f32>() * TAU;
+
+                // the enemy starts on the orbit rather than at the sampled spot, which may put it
+                // inside an obstacle
+                for _ in 0..MAX_SPAWN_ATTEMPTS {
+                    if is_free(center + Vec2::from_polar(orbit_radius, angle)) {
+                        break;
+                    }
+
+                    angle = rng.random::<f32>() * TAU;
+                }
+
+                builder.add(Orbit {
+                    center,
+                    radius: orbit_radius,
+                    angle,
+                    clockwise,
+                });
+
+                center + Vec2::from_polar(orbit_radius, angle)
+            }
+            Self::WallHugging { clockwise } => {
+                let (pos, normal) = snap_to_edge(sampled_pos, radius, bounds);
+
+                builder.add(WallHugging { clockwise });
+                builder.add(Direction(wall_tangent(normal, clockwise)));
+
+                pos
+            }
+        }
+    }
+}
+
+/// Removes every behavior-specific component from an enemy so a different behavior can be added.
+pub fn clear_components(world: &mut World, entity: Entity) {
+    let _ = world.remove_one::<Homing>(entity);
+    let _ = world.remove_one::<Wavy>(entity);
+    let _ = world.remove_one::<Dasher>(entity);
+    let _ = world.remove_one::<StopAndGo>(entity);
+    let _ = world.remove_one::<Orbit>(entity);
+    let _ = world.remove_one::<WallHugging>(entity);
+    let _ = world.remove_one::<BounceOffBounds>(entity);
+}
+
+/// Moves an orbit's center so the whole orbit stays inside the bounds. An orbit that's too
+/// large for the bounds is centered on that axis instead.
+fn orbit_center(pos: Vec2, reach: f32, bounds: &Rect) -> Vec2 {
+    let clamp_axis = |value: f32, min: f32, max: f32| match min + reach <= max - reach {
+        true => value.clamp(min + reach, max - reach),
+        false => (min + max) / 2.0,
+    };
+
+    Vec2::new(
+        clamp_axis(pos.x, bounds.left(), bounds.right()),
+        clamp_axis(pos.y, bounds.top(), bounds.bottom()),
+    )
+}
+
+/// Whether an orbit of `orbit_radius`, traced by an enemy of `radius`, fits inside the bounds.
+pub fn orbit_fits(orbit_radius: f32, radius: f32, bounds: &Rect) -> bool {
+    let diameter = (orbit_radius + radius) * 2.0;
+
+    diameter <= bounds.w && diameter <= bounds.h
+}
+
+/// Moves a point onto the closest edge of the bounds, returning the new point and the outward normal of that edge.
+fn snap_to_edge(pos: Vec2, radius: f32, bounds: &Rect) -> (Vec2, Vec2) {
+    let edges = [
+        (
+            pos.x - bounds.left(),
+            Vec2::new(bounds.left() + radius, pos.y),
+            Vec2::LEFT,
+        ),
+        (
+            bounds.right() - pos.x,
+            Vec2::new(bounds.right() - radius, pos.y),
+            Vec2::RIGHT,
+        ),
+        (
+            pos.y - bounds.top(),
+            Vec2::new(pos.x, bounds.top() + radius),
+            Vec2::DOWN,
+        ),
+        (
+            bounds.bottom() - pos.y,
+            Vec2::new(pos.x, bounds.bottom() - radius),
+            Vec2::UP,
+        ),
+    ];
+
+    let (_, pos, normal) = edges
+        .into_iter()
+        .min_by(|a, b| a.0.total_cmp(&b.0))
+        .unwrap();
+
+    (pos, normal)
+}
+
+pub fn wall_tangent(normal: Vec2, clockwise: bool) -> Vec2 {
+    if clockwise {
+        Vec2::new(normal.y, -normal.x)
+    } else {
+        Vec2::new(-normal.y, normal.x)
+    }
+}
+
+#[derive(Deserialize)]
+pub enum EnemyBehaviorData {
+    Bouncing,
+    Homing {
+        range: Option<f32>,
+        turn_speed: Option<f32>,
+    },
+    Wavy {
+        amplitude: Option<f32>,
+        frequency: Option<f32>,
+    },
+    Dasher {
+        dash_time: Option<f32>,
+        rest_time: Option<f32>,
+        dash_multiplier: Option<f32>,
+        range: Option<f32>,
+    },
+    StopAndGo {
+        move_time: Option<f32>,
+        stop_time: Option<f32>,
+    },
+    Orbit {
+        radius: Option<f32>,
+        clockwise: Option<bool>,
+    },
+    WallHugging {
+        clockwise: Option<bool>,
+    },
+}
@@ -0,0 +1,220 @@
+use super::{
+    area::EnemyGroup,
+    dynamic_wall::{DynamicWall, solid_walls},
+};
+use crate::physics::{rect::Rect, shape::Shape, vec2::Vec2};
+use rand::Rng;
+use serde::{
+    Deserialize, Deserializer,
+    de::{MapAccess, Visitor, value::MapAccessDeserializer},
+};
+use std::{
+    f32::consts::{FRAC_PI_2, FRAC_PI_4, PI},
+    fmt,
+};
+
+/// Spawn positions tried per enemy before giving up, for areas covered by safe zones or walls.
+pub const MAX_SPAWN_ATTEMPTS: u32 = 1000;
+
+/// Area geometry enemies can't be spawned inside of, unless their group flags allow it.
+pub struct SpawnObstacles<'a> {
+    pub safe_zones: &'a [Shape],
+    pub inner_walls: &'a [Shape],
+    pub dynamic_walls: &'a [DynamicWall],
+}
+
+impl SpawnObstacles<'_> {
+    /// Samples the part of the group's spawn region inside `region`, or all of `region` if
+    /// the group has none, until a free spot is found. Returns `None` after
+    /// `MAX_SPAWN_ATTEMPTS` failed samples.
+    pub fn find_position(
+        &self,
+        group: &EnemyGroup,
+        region: &Rect,
+        size: f32,
+        rng: &mut impl Rng,
+    ) -> Option<Vec2> {
+        (0..MAX_SPAWN_ATTEMPTS).find_map(|_| {
+            let pos = match group.spawn_region.is_empty() {
+                true => region.random_inside(rng),
+                false => random_in_shapes(&group.spawn_region, rng)
+                    .filter(|pos| region.contains(*pos))?,
+            };
+
+            self.is_free(group, pos, size).then_some(pos)
+        })
+    }
+
+    /// Whether an enemy of the group may be placed at `pos` without starting in a safe zone
+    /// or wall it isn't allowed to enter.
+    pub fn is_free(&self, group: &EnemyGroup, pos: Vec2, size: f32) -> bool {
+        let in_safe_zone = !group.flags.enter_safe_zones
+            && self
+                .safe_zones
+                .iter()
+                .any(|zone| zone.contains_circle(pos, size));
+
+        let in_wall = !group.flags.pass_through_walls
+            && solid_walls(self.inner_walls, self.dynamic_walls)
+                .any(|wall| wall.contains_circle(pos, size));
+
+        !in_safe_zone && !in_wall
+    }
+}
+
+/// Enemy stat rolled once per enemy, anywhere between `min` and `max`.
+#[derive(Clone, Copy, Debug)]
+pub struct StatRange {
+    pub min: f32,
+    pub max: f32,
+}
+
+impl StatRange {
+    pub fn new(data: StatRangeData) -> Self {
+        match data {
+            StatRangeData::Fixed(value) => Self::fixed(value),
+            StatRangeData::Range { min, max } => Self {
+                min: min.min(max),
+                max: min.max(max),
+            },
+        }
+    }
+
+    pub fn fixed(value: f32) -> Self {
+        Self {
+            min: value,
+            max: value,
+        }
+    }
+
+    pub fn roll(&self, rng: &mut impl Rng) -> f32 {
+        match self.min < self.max {
+            true => rng.random_range(self.min..=self.max),
+            false => self.min,
+        }
+    }
+}
+
+/// Which way enemies of a group face when they're spawned.
+#[derive(Clone, Copy, Debug)]
+pub enum SpawnDirection {
+    Random,
+    /// Angle in radians.
+    Angle(f32),
+    /// Any angle in the range, in radians.
+    AngleRange(StatRange),
+    Horizontal,
+    Vertical,
+    Diagonal,
+}
+
+impl SpawnDirection {
+    pub fn new(data: SpawnDirectionData) -> Self {
+        match data {
+            SpawnDirectionData::Random => Self::Random,
+            SpawnDirectionData::Angle(angle) => Self::Angle(angle.to_radians()),
+            SpawnDirectionData::AngleRange { min, max } => {
+                Self::AngleRange(StatRange::new(StatRangeData::Range {
+                    min: min.to_radians(),
+                    max: max.to_radians(),
+                }))
+            }
+            SpawnDirectionData::Horizontal => Self::Horizontal,
+            SpawnDirectionData::Vertical => Self::Vertical,
+            SpawnDirectionData::Diagonal => Self::Diagonal,
+        }
+    }
+
+    pub fn pick(&self, rng: &mut impl Rng) -> Vec2 {
+        let angle = match self {
+            Self::Random => return Vec2::random_unit(rng),
+            Self::Angle(angle) => *angle,
+            Self::AngleRange(range) => range.roll(rng),
+            Self::Horizontal => rng.random_range(0..2) as f32 * PI,
+            Self::Vertical => FRAC_PI_2 + rng.random_range(0..2) as f32 * PI,
+            Self::Diagonal => FRAC_PI_4 + rng.random_range(0..4) as f32 * FRAC_PI_2,
+        };
+
+        Vec2::from_polar(1.0, angle)
+    }
+}
+
+/// Picks a random point in one of the shapes, with larger shapes picked more often.
+/// Returns `None` when the sampled point misses the shape, so callers retry.
+fn random_in_shapes(shapes: &[Shape], rng: &mut impl Rng) -> Option<Vec2> {
+    let bounds: Vec<Rect> = shapes.iter().map(Shape::bounding_rect).collect();
+    let total: f32 = bounds.iter().map(|rect| rect.w * rect.h).sum();
+
+    let mut pick = rng.random::<f32>() * total;
+
+    for (shape, rect) in shapes.iter().zip(&bounds) {
+        pick -= rect.w * rect.h;
+
+        if pick <= 0.0 {
+            let pos = rect.random_inside(rng);
+            return shape.contains(pos).then_some(pos);
+        }
+    }
+
+    None
+}
+
+/// Either a single value or a `{ min, max }` range.
+pub enum StatRangeData {
+    Fixed(f32),
+    Range { min: f32, max: f32 },
+}
+
+#[derive(Deserialize)]
+struct StatRangeFields {
+    min: f32,
+    max: f32,
+}
+
+impl<'de> Deserialize<'de> for StatRangeData {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct StatRangeVisitor;
+
+        impl<'de> Visitor<'de> for StatRangeVisitor {
+            type Value = StatRangeData;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a number or a min/max range")
+            }
+
+            fn visit_f64<E>(self, value: f64) -> Result<Self::Value, E> {
+                Ok(StatRangeData::Fixed(value as f32))
+            }
+
+            fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E> {
+                Ok(StatRangeData::Fixed(value as f32))
+            }
+
+            fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E> {
+                Ok(StatRangeData::Fixed(value as f32))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+                StatRangeFields::deserialize(MapAccessDeserializer::new(map)).map(|range| {
+                    StatRangeData::Range {
+                        min: range.min,
+                        max: range.max,
+                    }
+                })
+            }
+        }
+
+        deserializer.deserialize_any(StatRangeVisitor)
+    }
+}
+
+/// Angles are in degrees.
+#[derive(Deserialize)]
+pub enum SpawnDirectionData {
+    Random,
+    Angle(f32),
+    AngleRange { min: f32, max: f32 },
+    Horizontal,
+    Vertical,
+    Diagonal,
+}
@@ -0,0 +1,84 @@
+use super::components::Color;
+use crate::physics::{
+    shape::{Shape, ShapeData},
+    vec2::Vec2,
+};
+use serde::Deserialize;
+
+#[derive(Clone, Copy)]
+pub enum ForceKind {
+    /// Accelerates entities in a fixed direction. The pushed velocity builds up while inside
+    /// the zone and fades out after leaving it.
+    Wind { acceleration: Vec2 },
+    /// Moves entities at a fixed velocity while they're inside the zone, with no build-up.
+    Conveyor { velocity: Vec2 },
+    /// Accelerates entities towards the zone center, or away from it with a negative strength.
+    Gravity { center: Vec2, strength: f32 },
+}
+
+impl ForceKind {
+    fn new(data: ForceKindData, shape: &Shape) -> Self {
+        match data {
+            ForceKindData::Wind { angle, strength } => Self::Wind {
+                acceleration: Vec2::from_polar(strength, angle.to_radians()),
+            },
+            ForceKindData::Conveyor { angle, speed } => Self::Conveyor {
+                velocity: Vec2::from_polar(speed, angle.to_radians()),
+            },
+            ForceKindData::Gravity { strength } => Self::Gravity {
+                center: shape.center(),
+                strength,
+            },
+        }
+    }
+
+    fn default_color(&self) -> Color {
+        match self {
+            Self::Wind { .. } => Color::rgba(150, 220, 255, 50),
+            Self::Conveyor { .. } => Color::rgba(120, 120, 120, 50),
+            Self::Gravity { .. } => Color::rgba(90, 0, 150, 50),
+        }
+    }
+}
+
+/// Area region that pushes heroes, and optionally enemies, on top of their own movement.
+#[derive(Clone)]
+pub struct ForceZone {
+    pub shape: Shape,
+    pub kind: ForceKind,
+    pub affects_enemies: bool,
+    pub color: Color,
+}
+
+impl ForceZone {
+    pub fn new(data: ForceZoneData) -> Self {
+        let shape = Shape::new(data.shape);
+        let kind = ForceKind::new(data.force, &shape);
+
+        Self {
+            color: data
+                .color
+                .map(Color::from)
+                .unwrap_or_else(|| kind.default_color()),
+            shape,
+            kind,
+            affects_enemies: data.affects_enemies.unwrap_or(false),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct ForceZoneData {
+    pub shape: ShapeData,
+    pub force: ForceKindData,
+    pub affects_enemies: Option<bool>,
+    pub color: Option<String>,
+}
+
+/// Angles are in degrees.
+#[derive(Deserialize)]
+pub enum ForceKindData {
+    Wind { angle: f32, strength: f32 },
+    Conveyor { angle: f32, speed: f32 },
+    Gravity { strength: f32 },
+}
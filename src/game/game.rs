@@ -1,13 +1,14 @@
 use super::{
-    area::{Area, AreaKey},
-    components::{CrossingPortal, Downed, Position},
+    area::{Area, AreaKey, LockedAreas},
+    area_actor::{AreaActor, AreaHandle, HibernationSettings, ReceiveError},
+    hero_class::HeroClass,
     map_table::try_get_map,
-    systems::*,
+    timestep::TimestepSettings,
 };
 use crate::{
     config::CONFIG,
     game::{
-        components::Timer,
+        components::{HeroProgress, Timer},
         player::PlayerId,
         transfer_request::{TransferRequest, TransferTarget},
     },
@@ -19,43 +20,68 @@ use crate::{
     physics::vec2::Vec2,
 };
 use anyhow::Result;
-use std::{collections::HashMap, sync::Arc, time::Duration};
-use tokio::{
-    join,
-    sync::{
-        Mutex, broadcast,
-        mpsc::{self},
-    },
-    time::{Instant, interval},
+use arc_swap::ArcSwap;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::Duration,
 };
+use tokio::sync::{broadcast, mpsc, oneshot};
+
+type AreaRegistry = Arc<ArcSwap<HashMap<AreaKey, AreaHandle>>>;
+
+/// Requests that change which area a hero is in. They're handled one at a time by the
+/// coordinator task, while movement input goes straight to the area actors.
+enum GameCommand {
+    Spawn {
+        class: &'static HeroClass,
+        reply: oneshot::Sender<Result<GameSpawnResult>>,
+    },
+    Despawn(PlayerId),
+    Reset(PlayerId),
+}
 
+/// Coordinates spawns and transfers between areas. Each area runs in its own actor,
+/// so this only keeps track of which areas are open.
 struct Game {
-    areas: HashMap<AreaKey, Arc<Mutex<Area>>>,
+    areas: AreaRegistry,
 
     spawn_area_key: AreaKey,
 
     output_tx: broadcast::Sender<GameOutputMessage>,
 
-    transfer_tx: mpsc::Sender<TransferRequest>,
-    transfer_queue: Vec<PlayerId>,
+    transfer_tx: mpsc::UnboundedSender<TransferRequest>,
 
     render_tx: mpsc::Sender<AreaRenderMessage>,
     status_tx: mpsc::Sender<PlayerStatusMessage>,
-
-    frame_duration: Duration,
+    refusal_tx: mpsc::Sender<PortalRefusedMessage>,
+    close_tx: mpsc::UnboundedSender<AreaKey>,
+    locked_areas: LockedAreas,
+
+    timestep: TimestepSettings,
+    hibernation: HibernationSettings,
+    spawn_protection: f32,
+    spatial_hash_cell_size: f32,
 }
 
 impl Game {
     pub fn new() -> GameHandle {
-        let (transfer_tx, mut transfer_rx) = mpsc::channel::<TransferRequest>(8);
+        let (command_tx, mut command_rx) = mpsc::unbounded_channel::<GameCommand>();
+        let (transfer_tx, mut transfer_rx) = mpsc::unbounded_channel::<TransferRequest>();
+        let (close_tx, mut close_rx) = mpsc::unbounded_channel::<AreaKey>();
         let (render_tx, mut render_rx) = mpsc::channel::<AreaRenderMessage>(64);
         let (status_tx, mut status_rx) = mpsc::channel::<PlayerStatusMessage>(64);
+        let (refusal_tx, mut refusal_rx) = mpsc::channel::<PortalRefusedMessage>(64);
 
         let (output_tx, output_rx) = broadcast::channel(64);
 
         let config = &CONFIG.game;
 
-        let frame_duration = Duration::from_secs_f32(1.0 / config.simulation_framerate);
+        let timestep = TimestepSettings::new(
+            config.simulation_framerate,
+            config.render_framerate,
+            config.max_catch_up_steps,
+        );
 
         let spawn_map_id = config
             .spawn_map
@@ -68,26 +94,37 @@ impl Game {
             .key
             .clone();
 
-        let game = Game {
-            areas: HashMap::new(),
+        let areas: AreaRegistry = Arc::new(ArcSwap::from_pointee(HashMap::new()));
+
+        let mut game = Game {
+            areas: areas.clone(),
             spawn_area_key,
             output_tx: output_tx.clone(),
-            transfer_tx: transfer_tx.clone(),
-            transfer_queue: Vec::new(),
+            transfer_tx,
             render_tx,
             status_tx,
-            frame_duration,
+            refusal_tx,
+            close_tx,
+            locked_areas: Arc::new(ArcSwap::from_pointee(HashSet::new())),
+            timestep,
+            hibernation: HibernationSettings {
+                grace_period: Duration::from_secs_f32(config.area_grace_period.max(0.0)),
+                pause_when_empty: config.pause_empty_areas,
+            },
+            spawn_protection: config.spawn_protection.max(0.0),
+            spatial_hash_cell_size: config.spatial_hash_cell_size,
         };
 
-        let arc = Arc::new(Mutex::new(game));
-        let transfer_arc = arc.clone();
-        let handle_arc = arc.clone();
-
         tokio::spawn(async move {
-            while let Some(req) = transfer_rx.recv().await {
-                let mut game = transfer_arc.lock().await;
-
-                let _ = game.transfer_hero(req).await;
+            loop {
+                tokio::select! {
+                    Some(command) = command_rx.recv() => game.handle_command(command).await,
+                    Some(req) = transfer_rx.recv() => {
+                        let _ = game.transfer_hero(req).await;
+                    }
+                    Some(key) = close_rx.recv() => game.close_empty_area(&key).await,
+                    else => break,
+                }
             }
         });
 
@@ -101,6 +138,7 @@ impl Game {
         }
 
         {
+            let output_tx = output_tx.clone();
             tokio::spawn(async move {
                 while let Some(msg) = status_rx.recv().await {
                     let _ = output_tx.send(GameOutputMessage::PlayerStatus(msg));
@@ -108,39 +146,61 @@ impl Game {
             });
         }
 
-        GameHandle::new(handle_arc, output_rx)
+        {
+            tokio::spawn(async move {
+                while let Some(msg) = refusal_rx.recv().await {
+                    let _ = output_tx.send(GameOutputMessage::PortalRefused(msg));
+                }
+            });
+        }
+
+        GameHandle::new(command_tx, areas, output_rx)
+    }
+
+    async fn handle_command(&mut self, command: GameCommand) {
+        match command {
+            GameCommand::Spawn { class, reply } => {
+                let _ = reply.send(self.handle_spawn_request(class).await);
+            }
+            GameCommand::Despawn(id) => {
+                let _ = self.despawn_hero(id).await;
+            }
+            GameCommand::Reset(id) => {
+                let _ = self.reset_hero(id).await;
+            }
+        }
     }
 
-    async fn handle_spawn_request(&mut self) -> GameSpawnResult {
-        let area = self.get_spawn_area();
-        let mut area = area.lock().await;
+    async fn handle_spawn_request(&mut self, class: &'static HeroClass) -> Result<GameSpawnResult> {
+        let area_key = self.spawn_area_key.clone();
+        let area = self.get_spawn_area()?;
 
-        let (entity, timestamp) = area.spawn_player();
+        let spawned = area.spawn_player(class).await?;
 
-        Logger::info(format!("Spawning hero..."));
+        Logger::info(format!("Spawning {} hero...", class.name));
 
         let player_id = PlayerId {
-            entity,
-            area: area.key.clone(),
+            entity: spawned.entity,
+            area: area_key,
         };
 
         let area_definition = AreaDefinitionMessage {
             id: player_id.clone(),
-            data: area.definition_packet(),
+            data: spawned.definition,
         };
 
         let _ = self
             .output_tx
             .send(GameOutputMessage::AreaDefinition(area_definition));
 
-        GameSpawnResult {
+        Ok(GameSpawnResult {
             player_id,
-            area_info: AreaInfo::from_area(&area),
-            timestamp,
-        }
+            area_info: spawned.area_info,
+            timestamp: spawned.timestamp,
+        })
     }
 
-    fn try_create_area(&mut self, key: &AreaKey) -> Result<Arc<Mutex<Area>>> {
+    fn try_create_area(&mut self, key: &AreaKey) -> Result<AreaHandle> {
         let map_id = key.map_id();
 
         let map =
@@ -152,135 +212,114 @@ impl Game {
 
         let area = Area::new(
             template,
+            self.spatial_hash_cell_size,
             self.transfer_tx.clone(),
             self.render_tx.clone(),
             self.status_tx.clone(),
+            self.refusal_tx.clone(),
+            self.locked_areas.clone(),
+        );
+
+        let seed = area.seed;
+
+        let handle = AreaActor::spawn(
+            area,
+            self.timestep,
+            self.hibernation,
+            self.spawn_protection,
+            self.close_tx.clone(),
         );
 
-        let area = Arc::new(Mutex::new(area));
-        Self::start_update_loop(area.clone(), self.frame_duration);
-        self.areas.insert(key.clone(), area.clone());
+        self.areas.rcu(|areas| {
+            let mut areas = HashMap::clone(areas);
+            areas.insert(key.clone(), handle.clone());
+            areas
+        });
 
         Logger::debug(format!(
-            "Area {} opened. Loaded areas: {:?}",
+            "Area {} opened with seed {}. Loaded areas: {:?}",
             key,
-            self.areas.keys().collect::<Vec<_>>()
+            seed,
+            self.areas.load().keys().collect::<Vec<_>>()
         ));
 
-        Ok(area)
+        Ok(handle)
     }
 
-    pub fn get_or_create_area(&mut self, key: &AreaKey) -> Result<Arc<Mutex<Area>>> {
-        if let Some(area) = self.areas.get(&key) {
+    pub fn get_or_create_area(&mut self, key: &AreaKey) -> Result<AreaHandle> {
+        if let Some(area) = self.areas.load().get(key) {
             return Ok(area.clone());
         }
 
         self.try_create_area(key)
     }
 
-    fn close_area(&mut self, key: &AreaKey) {
-        self.areas.remove(key);
+    /// Closes an area whose grace period ran out, unless a hero entered it in the meantime.
+    async fn close_empty_area(&mut self, key: &AreaKey) {
+        let Some(area) = self.areas.load().get(key).cloned() else {
+            return;
+        };
+
+        if !area.close_if_empty().await {
+            return;
+        }
+
+        self.areas.rcu(|areas| {
+            let mut areas = HashMap::clone(areas);
+            areas.remove(key);
+            areas
+        });
+
+        self.locked_areas.rcu(|areas| {
+            let mut areas = HashSet::clone(areas);
+            areas.remove(key);
+            areas
+        });
 
         Logger::debug(format!(
             "Area {} closed. Loaded areas: {:?}",
             key,
-            self.areas.keys().collect::<Vec<_>>()
+            self.areas.load().keys().collect::<Vec<_>>()
         ));
     }
 
-    fn get_spawn_area(&mut self) -> Arc<Mutex<Area>> {
+    fn get_spawn_area(&mut self) -> Result<AreaHandle> {
         let spawn_area_key = self.spawn_area_key.clone();
         self.get_or_create_area(&spawn_area_key)
-            .unwrap_or_else(|_| panic!("Start area '{spawn_area_key}' not found"))
     }
 
-    async fn update_area(area: &mut Area, delta_time: f32) {
-        area.frame_count += 1;
-        area.time += delta_time;
-        area.delta_time = delta_time;
-
-        system_update_energy(area);
-
-        system_update_velocity(area);
-        system_evaluate_target_position(area);
-
-        system_bounds_check(area);
-        system_inner_wall_collision(area);
-        system_safe_zone_collision(area);
-
-        system_commit_position(area);
-
-        system_portals(area).await;
-        system_hero_collision(area).await;
-        system_enemy_collision(area).await;
-
-        system_render(area);
-    }
-
-    fn start_update_loop(area: Arc<Mutex<Area>>, frame_duration: Duration) {
-        let area_clone = area.clone();
-
-        let handle = tokio::spawn(async move {
-            let mut last_time = Instant::now();
-
-            let mut interval = interval(frame_duration);
-
-            loop {
-                {
-                    let mut area = area_clone.lock().await;
-                    Self::update_area(&mut area, last_time.elapsed().as_secs_f32()).await;
-
-                    if let Some(packet) = &&area.render_packet {
-                        let _ = area
-                            .render_tx
-                            .send(AreaRenderMessage {
-                                key: area.key.clone(),
-                                packet: packet.clone(),
-                            })
-                            .await;
-                    }
-                }
-
-                last_time = Instant::now();
-                interval.tick().await;
-            }
-        });
-
-        area.try_lock().unwrap().loop_handle = Some(handle.abort_handle());
+    /// Looks up an area that's already open, like the one a hero is currently in.
+    fn get_open_area(&self, key: &AreaKey) -> Result<AreaHandle> {
+        self.areas
+            .load()
+            .get(key)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("Area '{}' is not open", key))
     }
 
     pub async fn despawn_hero(&mut self, player_id: PlayerId) -> Result<()> {
-        let area_arc = self.get_or_create_area(&player_id.area)?;
-        let mut area = area_arc.lock().await;
+        let area = self.get_open_area(&player_id.area)?;
 
-        let (_, should_close) = area.despawn_player(player_id.entity);
+        area.despawn_player(player_id.entity).await?;
 
         Logger::info(format!("Despawning player @{}...", player_id));
 
-        if should_close {
-            self.close_area(&area.key);
-        }
-
         Ok(())
     }
 
+    /// Sends the hero back to the spawn area and restarts its timer.
+    /// Victories and VP from the current run are cleared so they can be earned again,
+    /// while experience and levels are kept.
     pub async fn reset_hero(&mut self, player: PlayerId) -> Result<()> {
-        {
-            let area = self.get_or_create_area(&player.area)?;
-            let mut area = area.lock().await;
-
-            let _ = area.world.remove_one::<Downed>(player.entity);
-
-            if let Ok(timer) = area.world.query_one_mut::<&mut Timer>(player.entity) {
-                timer.reset();
-
-                let _ = self
-                    .output_tx
-                    .send(GameOutputMessage::TimerUpdate(TimerUpdateMessage {
-                        player_id: player.clone(),
-                        timestamp: timer.timestamp(),
-                    }));
-            }
+        let area = self.get_open_area(&player.area)?;
+
+        if let Some(timestamp) = area.reset_timer(player.entity).await? {
+            let _ = self
+                .output_tx
+                .send(GameOutputMessage::TimerUpdate(TimerUpdateMessage {
+                    player_id: player.clone(),
+                    timestamp,
+                }));
         }
 
         let req = TransferRequest {
@@ -305,175 +344,150 @@ impl Game {
         Ok(())
     }
 
-    pub async fn transfer_hero(&mut self, req: TransferRequest) -> Result<()> {
-        if !self.transfer_queue.contains(&req.player) {
-            self.transfer_queue.push(req.player.clone());
-        }
-
-        let target_key = match req.target {
+    fn get_target_area(&mut self, target: &TransferTarget) -> Result<(AreaKey, AreaHandle)> {
+        let key = match target {
             TransferTarget::Spawn => self.spawn_area_key.clone(),
-            TransferTarget::MapStart(ref map_id) => try_get_map(&map_id)
+            TransferTarget::MapStart(map_id) => try_get_map(map_id)
                 .ok_or_else(|| anyhow::anyhow!("Map '{}' not found", map_id))?
                 .get_start_area()
                 .key
                 .clone(),
-            TransferTarget::Area(ref key) => key.clone(),
+            TransferTarget::Area(key) => key.clone(),
         };
 
-        if target_key == req.player.area {
-            return self.move_hero_across_area(req).await;
-        }
-
-        let target_area_arc = self.get_or_create_area(&target_key)?;
-
-        let player_area = self.get_or_create_area(&req.player.area)?;
-
-        let (mut player_area, mut target_area) = join!(player_area.lock(), target_area_arc.lock());
-
-        let (taken_entity, should_close) = player_area.despawn_player(req.player.entity);
-        let entity = taken_entity?;
-        let entity = target_area.world.spawn(entity);
+        let area = self.get_or_create_area(&key)?;
 
-        let _ = target_area.world.remove_one::<CrossingPortal>(entity);
-
-        let target_pos = match req.target_pos {
-            Some(target_pos) => {
-                let target_x = target_pos.x.resolve(&target_area.bounds);
-                let target_y = target_pos.y.resolve(&target_area.bounds);
+        Ok((key, area))
+    }
 
-                Vec2::new(target_x, target_y)
+    pub async fn transfer_hero(&mut self, req: TransferRequest) -> Result<()> {
+        let player_area = self.get_open_area(&req.player.area)?;
+
+        let (target_key, target_area) = match self.get_target_area(&req.target) {
+            Ok(target) => target,
+            Err(err) => {
+                // the hero stays where it is, so it shouldn't stay immune as if it was leaving
+                player_area.cancel_crossing(req.player.entity);
+                return Err(err);
             }
-            None => target_area.spawn_pos,
         };
 
-        let world = &mut target_area.world;
-        let timer = world.query_one_mut::<&mut Timer>(entity).ok().cloned();
-
-        if should_close {
-            self.close_area(&player_area.key);
+        if target_key == req.player.area {
+            return player_area
+                .move_hero(req.player.entity, req.target_pos)
+                .await;
         }
 
-        drop(player_area);
+        let hero = player_area.take_hero(req.player.entity).await?;
 
-        let pos = target_area
-            .world
-            .query_one_mut::<&mut Position>(entity)
-            .unwrap();
+        let (target_key, arrived) = match target_area.receive_hero(hero, req.target_pos).await {
+            Ok(arrived) => (target_key, arrived),
+            Err(ReceiveError {
+                hero: Some(hero),
+                error,
+            }) => {
+                Logger::error(format!(
+                    "Could not move hero to area {target_key}, sending it back: {error}"
+                ));
 
-        pos.0 = target_pos;
+                let arrived = player_area
+                    .receive_hero(hero, None)
+                    .await
+                    .map_err(|err| err.error)?;
 
-        self.transfer_queue.swap_remove(
-            self.transfer_queue
-                .iter()
-                .position(|id| *id == req.player)
-                .unwrap(),
-        );
+                (req.player.area.clone(), arrived)
+            }
+            Err(ReceiveError { hero: None, error }) => return Err(error),
+        };
 
         let new_id = PlayerId {
-            entity,
+            entity: arrived.entity,
             area: target_key,
         };
 
-        let route_name = match &target_area.route_name {
-            Some(route) => route,
-            None => match &target_area.flags.final_victory {
-                true => &target_area.map_name,
-                false => &target_area.full_name,
-            },
-        }
-        .clone();
-
         let msg = PlayerTransferMessage {
             player_id: req.player.clone(),
             new_id,
-            area_info: AreaInfo::from_area(&target_area),
-            timer,
-            route_name,
+            area_info: arrived.area_info,
+            timer: arrived.timer,
+            route_name: arrived.route_name,
         };
 
         let _ = self.output_tx.send(GameOutputMessage::PlayerTransfer(msg));
 
         let msg = AreaDefinitionMessage {
             id: req.player,
-            data: target_area.definition_packet(),
+            data: arrived.definition,
         };
 
         let _ = self.output_tx.send(GameOutputMessage::AreaDefinition(msg));
 
         Ok(())
     }
-
-    pub async fn move_hero_across_area(&mut self, req: TransferRequest) -> Result<()> {
-        let area = self.get_or_create_area(&req.player.area)?;
-        let mut area = area.lock().await;
-        let bounds = area.bounds.clone();
-
-        let area_spawn_pos = area.spawn_pos;
-
-        let pos = area
-            .world
-            .query_one_mut::<&mut Position>(req.player.entity)?;
-
-        let target_pos = match req.target_pos {
-            Some(target_pos) => {
-                let target_x = target_pos.x.resolve(&bounds);
-                let target_y = target_pos.y.resolve(&bounds);
-
-                Vec2::new(target_x, target_y)
-            }
-            None => area_spawn_pos,
-        };
-
-        pos.0 = target_pos;
-
-        Ok(())
-    }
-
-    pub async fn send_input_update(&mut self, player_id: PlayerId, input: Vec2) -> Result<()> {
-        let area = self.get_or_create_area(&player_id.area)?;
-        let mut area = area.lock().await;
-
-        area.update_player_input(player_id.entity, input);
-
-        Ok(())
-    }
 }
 
 pub struct GameHandle {
-    game: Arc<Mutex<Game>>,
+    commands: mpsc::UnboundedSender<GameCommand>,
+    areas: AreaRegistry,
     pub output_rx: broadcast::Receiver<GameOutputMessage>,
 }
 
 impl GameHandle {
-    fn new(game: Arc<Mutex<Game>>, output_rx: broadcast::Receiver<GameOutputMessage>) -> Self {
-        Self { game, output_rx }
+    fn new(
+        commands: mpsc::UnboundedSender<GameCommand>,
+        areas: AreaRegistry,
+        output_rx: broadcast::Receiver<GameOutputMessage>,
+    ) -> Self {
+        Self {
+            commands,
+            areas,
+            output_rx,
+        }
     }
 
-    pub async fn send_spawn_request(&self) -> GameSpawnResult {
-        let mut game = self.game.lock().await;
-        game.handle_spawn_request().await
+    pub async fn send_spawn_request(&self, class: &'static HeroClass) -> Result<GameSpawnResult> {
+        let (reply, result) = oneshot::channel();
+
+        let _ = self.commands.send(GameCommand::Spawn { class, reply });
+
+        result
+            .await
+            .map_err(|_| anyhow::anyhow!("Game stopped before the hero was spawned"))?
     }
 
     pub async fn send_despawn_request(&self, id: PlayerId) {
-        let mut game = self.game.lock().await;
-        let _ = game.despawn_hero(id).await;
+        let _ = self.commands.send(GameCommand::Despawn(id));
     }
 
     pub async fn send_reset_request(&self, id: PlayerId) {
-        let mut game = self.game.lock().await;
-        let _ = game.reset_hero(id).await;
+        let _ = self.commands.send(GameCommand::Reset(id));
     }
 
     pub async fn send_input_update(&self, id: PlayerId, input: Vec2) {
-        let mut game = self.game.lock().await;
-        let _ = game.send_input_update(id, input).await;
+        if let Some(area) = self.areas.load().get(&id.area) {
+            area.update_input(id.entity, input);
+        }
+    }
+
+    pub async fn send_ability_request(&self, id: PlayerId, slot: usize) {
+        if let Some(area) = self.areas.load().get(&id.area) {
+            area.request_ability(id.entity, slot);
+        }
+    }
+
+    /// Keeps the hero's copy of its user's VP and victories current for portal conditions.
+    pub async fn send_progress_update(&self, id: PlayerId, vp: u32, victories: Vec<AreaKey>) {
+        if let Some(area) = self.areas.load().get(&id.area) {
+            area.update_progress(id.entity, HeroProgress { vp, victories });
+        }
     }
 }
 
 impl Clone for GameHandle {
     fn clone(&self) -> Self {
         Self {
-            game: self.game.clone(),
+            commands: self.commands.clone(),
+            areas: self.areas.clone(),
             output_rx: self.output_rx.resubscribe(),
         }
     }
@@ -509,6 +523,7 @@ pub enum GameOutputMessage {
     PlayerReset(PlayerId),
     PlayerStatus(PlayerStatusMessage),
     TimerUpdate(TimerUpdateMessage),
+    PortalRefused(PortalRefusedMessage),
 }
 
 pub struct GameSpawnResult {
@@ -537,3 +552,9 @@ pub struct TimerUpdateMessage {
     pub player_id: PlayerId,
     pub timestamp: u64,
 }
+
+#[derive(Clone)]
+pub struct PortalRefusedMessage {
+    pub player_id: PlayerId,
+    pub reason: String,
+}
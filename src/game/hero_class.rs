@@ -0,0 +1,56 @@
+use super::{
+    abilities::{Ability, AbilityData, default_abilities},
+    components::Color,
+    surface::{Inertia, InertiaData},
+};
+use serde::Deserialize;
+
+pub struct HeroClass {
+    pub id: String,
+    pub name: String,
+
+    pub speed: f32,
+    pub size: f32,
+    pub regen: f32,
+    pub max_energy: f32,
+
+    /// Heroes without a class color get a random one when spawned.
+    pub color: Option<Color>,
+
+    /// Heroes without inertia move instantly, unless they're on a slippery surface.
+    pub inertia: Option<Inertia>,
+
+    pub abilities: Vec<Ability>,
+}
+
+impl HeroClass {
+    pub fn new(data: HeroClassData) -> Self {
+        Self {
+            id: data.id,
+            name: data.name,
+            speed: data.speed.unwrap_or(17.0),
+            size: data.size.unwrap_or(1.0),
+            regen: data.regen.unwrap_or(10.0),
+            max_energy: data.max_energy.unwrap_or(100.0),
+            color: data.color.map(Color::from),
+            inertia: data.inertia.map(Inertia::new),
+            abilities: data
+                .abilities
+                .map(|abilities| abilities.into_iter().map(Ability::new).collect())
+                .unwrap_or_else(default_abilities),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct HeroClassData {
+    pub id: String,
+    pub name: String,
+    pub speed: Option<f32>,
+    pub size: Option<f32>,
+    pub regen: Option<f32>,
+    pub max_energy: Option<f32>,
+    pub color: Option<String>,
+    pub inertia: Option<InertiaData>,
+    pub abilities: Option<Vec<AbilityData>>,
+}
@@ -0,0 +1,55 @@
+use super::hero_class::HeroClass;
+use crate::{config::CONFIG, logger::Logger, parsing::parse_hero_classes};
+use itertools::Itertools;
+use std::sync::LazyLock;
+
+static HERO_CLASSES: LazyLock<Vec<HeroClass>> = LazyLock::new(fill_hero_class_table);
+
+fn fill_hero_class_table() -> Vec<HeroClass> {
+    let path = &CONFIG.heroes.path;
+
+    let class_datas = parse_hero_classes(path)
+        .unwrap_or_else(|err| panic!("Could not parse hero classes from {path}: {err}"));
+
+    if class_datas.is_empty() {
+        panic!("No hero classes defined in {path}");
+    }
+
+    let duplicates: Vec<String> = class_datas
+        .iter()
+        .map(|data| data.id.clone())
+        .duplicates()
+        .collect();
+
+    if !duplicates.is_empty() {
+        Logger::error(format!(
+            "Hero class ID collision detected. Two classes can't share the same ID. {duplicates:?}"
+        ));
+    }
+
+    let classes: Vec<HeroClass> = class_datas
+        .into_iter()
+        .unique_by(|data| data.id.clone())
+        .map(HeroClass::new)
+        .collect();
+
+    Logger::debug(format!(
+        "Loaded hero classes: {:?}",
+        classes.iter().map(|class| &class.id).collect::<Vec<_>>()
+    ));
+
+    classes
+}
+
+pub fn try_get_hero_class(id: &str) -> Option<&'static HeroClass> {
+    HERO_CLASSES.iter().find(|class| class.id == id)
+}
+
+/// The first class in the heroes file, used when a client doesn't pick one.
+pub fn get_default_hero_class() -> &'static HeroClass {
+    &HERO_CLASSES[0]
+}
+
+pub fn get_hero_class_list() -> Vec<&'static HeroClass> {
+    HERO_CLASSES.iter().collect()
+}
@@ -1,7 +1,9 @@
 use super::{
     area::{AreaCreationContext, AreaData, AreaTemplate},
     components::Color,
+    map_table::map_exists,
 };
+use anyhow::Result;
 use serde::Deserialize;
 use std::collections::HashMap;
 
@@ -19,11 +21,20 @@ pub struct MapTemplate {
 }
 
 impl MapTemplate {
-    pub fn new(data: MapData) -> Self {
+    pub fn new(data: MapData) -> Result<Self> {
+        Self::with_known_maps(data, &map_exists)
+    }
+
+    /// Like `new`, but maps that portals refer to are looked up with `map_exists` instead of
+    /// in the maps the server loads.
+    pub fn with_known_maps(data: MapData, map_exists: &dyn Fn(&str) -> bool) -> Result<Self> {
         let area_ctx = AreaCreationContext {
             map_id: data.id.clone(),
             map_name: data.name.clone(),
             background_color: data.background_color.clone(),
+            seed: data.seed,
+            death_timer: data.death_timer,
+            map_exists,
         };
 
         let areas: Vec<AreaTemplate> = data
@@ -31,7 +42,7 @@ impl MapTemplate {
             .into_iter()
             .enumerate()
             .map(|(order, area)| AreaTemplate::new(area, order as u16, &area_ctx))
-            .collect();
+            .collect::<Result<_>>()?;
 
         let alias_orders: HashMap<String, u16> = areas
             .iter()
@@ -42,13 +53,14 @@ impl MapTemplate {
         let start_area_order = data.start_area_order.unwrap_or(0);
 
         if areas.get(start_area_order as usize).is_none() {
-            panic!(
+            return Err(anyhow::anyhow!(
                 "Could not find area with order {} in map {} to set as start area",
-                start_area_order, data.id
-            );
+                start_area_order,
+                data.id
+            ));
         }
 
-        Self {
+        Ok(Self {
             id: data.id,
             name: data.name,
             background_color: data.background_color.into(),
@@ -56,7 +68,7 @@ impl MapTemplate {
             areas,
             start_area_order,
             alias_orders,
-        }
+        })
     }
 
     pub fn try_get_area(&self, order: usize) -> Option<&AreaTemplate> {
@@ -87,4 +99,7 @@ pub struct MapData {
     pub areas: Vec<AreaData>,
 
     pub start_area_order: Option<u16>,
+
+    pub seed: Option<u64>,
+    pub death_timer: Option<f32>,
 }
@@ -22,12 +22,10 @@ fn fill_map_ids() -> Vec<String> {
 }
 
 fn fill_map_table() -> HashMap<String, MapTemplate> {
-    let path = &CONFIG.maps.path;
-
     let map_datas: Vec<MapData> = MAP_IDS
         .iter()
         .map(|id| {
-            parse_map(&format!("{path}/{id}.yaml",))
+            parse_map(&map_file_path(id))
                 .unwrap_or_else(|err| panic!("Could not parse map {id}: {err}"))
         })
         .collect();
@@ -53,7 +51,9 @@ fn fill_map_table() -> HashMap<String, MapTemplate> {
         .unique_by(|d| d.id.clone())
         .into_iter()
         .map(|map| {
-            let template = MapTemplate::new(map);
+            let id = map.id.clone();
+            let template = MapTemplate::new(map)
+                .unwrap_or_else(|err| panic!("Could not load map {id}: {err}"));
             (template.id.clone(), template)
         })
         .collect();
@@ -106,6 +106,15 @@ pub fn get_map_list() -> Vec<&'static MapTemplate> {
     MAPS.values().collect()
 }
 
+/// Ids of the maps the server loads, taken from their file names.
+pub fn get_map_ids() -> &'static [String] {
+    &MAP_IDS
+}
+
+pub fn map_file_path(id: &str) -> String {
+    format!("{}/{id}.yaml", CONFIG.maps.path)
+}
+
 pub fn map_exists(id: &str) -> bool {
     MAP_IDS.iter().any(|map_id| map_id == id)
 }
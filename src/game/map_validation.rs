@@ -0,0 +1,887 @@
+use super::{
+    area::{AreaKey, AreaTemplate, EnemyGroup},
+    dynamic_wall::solid_walls,
+    enemy_behavior::{EnemyBehavior, orbit_fits},
+    enemy_spawn::SpawnObstacles,
+    hero_class_table::try_get_hero_class,
+    map::{MapData, MapTemplate},
+    map_table::{get_map_ids, map_file_path},
+    portal::{PortalConditionData, PortalData, PortalTargetData},
+};
+use crate::{
+    config::CONFIG,
+    parsing::parse_map,
+    physics::{rect::Rect, vec2::Vec2},
+};
+use rand::{SeedableRng, rngs::StdRng};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fmt,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+pub struct MapIssue {
+    pub severity: Severity,
+    pub file: String,
+    /// Order and name of the area the issue was found in, if it's not about the whole map.
+    pub area: Option<(u16, String)>,
+    pub message: String,
+}
+
+impl fmt::Display for MapIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+
+        write!(f, "{severity}: {}", self.file)?;
+
+        if let Some((order, name)) = &self.area {
+            write!(f, ", area {order} ({name})")?;
+        }
+
+        write!(f, ": {}", self.message)
+    }
+}
+
+/// Loads every map the server would load and reports the problems that would otherwise
+/// only show up at runtime, as panics or portals that silently do nothing.
+pub fn validate_maps() -> Vec<MapIssue> {
+    let mut report = Report::default();
+
+    let mut maps = Vec::new();
+    // maps that failed to parse are reported once, not again for every portal leading there
+    let mut broken = HashSet::new();
+
+    for id in get_map_ids() {
+        let file = map_file_path(id);
+
+        match parse_map(&file) {
+            Ok(data) => {
+                if data.id != *id {
+                    report.error(
+                        &file,
+                        None,
+                        format!(
+                            "map id '{}' doesn't match the file name, so portals targeting '{id}' can't find it",
+                            data.id
+                        ),
+                    );
+                }
+
+                maps.push((file, data));
+            }
+            Err(err) => {
+                report.error(&file, None, format!("could not parse map: {err}"));
+                broken.insert(id.clone());
+            }
+        }
+    }
+
+    let spawn_map = &CONFIG.game.spawn_map;
+
+    check_maps(maps, &broken, spawn_map.as_deref(), &mut report);
+
+    report.issues
+}
+
+/// Checks maps that were already read from their files. `broken` holds the ids of the maps
+/// that couldn't be read, so portals leading there aren't reported again.
+fn check_maps(
+    maps: Vec<(String, MapData)>,
+    broken: &HashSet<String>,
+    spawn_map_id: Option<&str>,
+    report: &mut Report,
+) {
+    let mut index: HashMap<String, MapIndex> = HashMap::new();
+
+    for (file, data) in &maps {
+        if index.contains_key(&data.id) {
+            report.error(file, None, format!("map id '{}' is used twice", data.id));
+            continue;
+        }
+
+        index.insert(data.id.clone(), MapIndex::new(file, data));
+    }
+
+    let mut links: Links = HashMap::new();
+    let mut templates = Vec::new();
+
+    let map_exists = |id: &str| index.contains_key(id) || broken.contains(id);
+
+    for (file, data) in maps {
+        if !check_map_data(&file, &data, &index, broken, &mut links, report) {
+            report.warning(
+                &file,
+                None,
+                "the map can't be loaded, so its areas were not checked".to_owned(),
+            );
+            continue;
+        }
+
+        match MapTemplate::with_known_maps(data, &map_exists) {
+            Ok(template) => {
+                for area in &template.areas {
+                    check_area(&file, area, report);
+                }
+
+                templates.push((file, template));
+            }
+            Err(err) => report.error(&file, None, format!("could not load map: {err}")),
+        }
+    }
+
+    for (file, template) in &templates {
+        for area in &template.areas {
+            check_portal_landings(file, area, &templates, &links, report);
+        }
+    }
+
+    check_reachability(spawn_map_id, &index, broken, &links, report);
+}
+
+/// Where the portals of each area lead, by portal number. Portals whose target can't be
+/// resolved are left out, they're reported while the links are collected.
+type Links = HashMap<AreaKey, Vec<(usize, AreaKey)>>;
+
+#[derive(Default)]
+struct Report {
+    issues: Vec<MapIssue>,
+}
+
+impl Report {
+    fn error(&mut self, file: &str, area: Option<(u16, &str)>, message: String) {
+        self.push(Severity::Error, file, area, message);
+    }
+
+    fn warning(&mut self, file: &str, area: Option<(u16, &str)>, message: String) {
+        self.push(Severity::Warning, file, area, message);
+    }
+
+    fn push(&mut self, severity: Severity, file: &str, area: Option<(u16, &str)>, message: String) {
+        self.issues.push(MapIssue {
+            severity,
+            file: file.to_owned(),
+            area: area.map(|(order, name)| (order, name.to_owned())),
+            message,
+        });
+    }
+}
+
+/// What portals in other maps need to know to resolve their targets.
+struct MapIndex {
+    file: String,
+    area_names: Vec<String>,
+    aliases: HashMap<String, u16>,
+    start_area_order: u16,
+}
+
+impl MapIndex {
+    fn new(file: &str, data: &MapData) -> Self {
+        Self {
+            file: file.to_owned(),
+            area_names: data
+                .areas
+                .iter()
+                .enumerate()
+                .map(|(order, area)| {
+                    area.name
+                        .clone()
+                        .unwrap_or_else(|| format!("Area {}", order + 1))
+                })
+                .collect(),
+            aliases: data
+                .areas
+                .iter()
+                .enumerate()
+                .filter_map(|(order, area)| Some((area.alias.clone()?, order as u16)))
+                .collect(),
+            start_area_order: data.start_area_order.unwrap_or(0),
+        }
+    }
+
+    fn area_count(&self) -> u16 {
+        self.area_names.len() as u16
+    }
+}
+
+/// Resolves a `map:order` or `map:alias` reference the same way portals do at runtime.
+fn resolve_area(id: &str, index: &HashMap<String, MapIndex>) -> Result<AreaKey, String> {
+    let (map_id, area) = id
+        .split_once(':')
+        .ok_or_else(|| format!("'{id}' is not a 'map:area' reference"))?;
+
+    let map = index
+        .get(map_id)
+        .ok_or_else(|| format!("'{id}' refers to unknown map '{map_id}'"))?;
+
+    let order = match area.parse::<u16>() {
+        Ok(order) => order,
+        Err(_) => *map
+            .aliases
+            .get(area)
+            .ok_or_else(|| format!("'{id}' refers to unknown area alias '{area}'"))?,
+    };
+
+    if order >= map.area_count() {
+        return Err(format!(
+            "'{id}' refers to area {order}, but map '{map_id}' only has {} areas",
+            map.area_count()
+        ));
+    }
+
+    Ok(AreaKey::new(map_id.to_owned(), order))
+}
+
+/// Checks what can be checked before building the map, and collects where its portals lead.
+/// Returns false if building the map would panic.
+fn check_map_data(
+    file: &str,
+    data: &MapData,
+    index: &HashMap<String, MapIndex>,
+    broken: &HashSet<String>,
+    links: &mut Links,
+    report: &mut Report,
+) -> bool {
+    if data.areas.is_empty() {
+        report.error(file, None, "map has no areas".to_owned());
+        return false;
+    }
+
+    let area_count = data.areas.len() as u16;
+    let mut loadable = true;
+
+    if let Some(start) = data.start_area_order
+        && start >= area_count
+    {
+        report.error(
+            file,
+            None,
+            format!("start area {start} doesn't exist, the map only has {area_count} areas"),
+        );
+        loadable = false;
+    }
+
+    for (order, area) in data.areas.iter().enumerate() {
+        let order = order as u16;
+        let name = area
+            .name
+            .clone()
+            .unwrap_or_else(|| format!("Area {}", order + 1));
+        let context = Some((order, name.as_str()));
+
+        let key = AreaKey::new(data.id.clone(), order);
+        let has_boss = area.boss.is_some();
+
+        for (number, portal) in area.portals.iter().flatten().enumerate() {
+            let target_map_id = match &portal.target {
+                PortalTargetData::Area(id) => id.split_once(':').map(|(map_id, _)| map_id),
+                PortalTargetData::Map(id) => Some(id.as_str()),
+                PortalTargetData::Previous | PortalTargetData::Next => None,
+            };
+
+            if let Some(map_id) = target_map_id
+                && broken.contains(map_id)
+            {
+                loadable &= !matches!(portal.target, PortalTargetData::Map(_));
+                continue;
+            }
+
+            let target = match &portal.target {
+                PortalTargetData::Area(id) => {
+                    resolve_area(id, index).map_err(|message| format!("target {message}"))
+                }
+                PortalTargetData::Map(id) => match index.get(id) {
+                    Some(map) => Ok(AreaKey::new(id.clone(), map.start_area_order)),
+                    None => {
+                        loadable = false;
+                        Err(format!("target map '{id}' doesn't exist"))
+                    }
+                },
+                PortalTargetData::Previous => {
+                    match order.checked_sub(1) {
+                        Some(previous) => Ok(AreaKey::new(data.id.clone(), previous)),
+                        None => {
+                            loadable = false;
+                            Err("target is the previous area, but this is the first area of the map".to_owned())
+                        }
+                    }
+                }
+                PortalTargetData::Next => match order + 1 < area_count {
+                    true => Ok(AreaKey::new(data.id.clone(), order + 1)),
+                    false => Err(
+                        "target is the next area, but this is the last area of the map".to_owned(),
+                    ),
+                },
+            };
+
+            match target {
+                Ok(target) => links.entry(key.clone()).or_default().push((number, target)),
+                Err(message) => report.error(file, context, format!("portal {number}: {message}")),
+            }
+
+            if portal.boss_exit.unwrap_or(false) && !has_boss {
+                report.warning(
+                    file,
+                    context,
+                    format!("portal {number} is a boss exit, but the area has no boss fight"),
+                );
+            }
+
+            loadable &= check_conditions(file, context, number, portal, index, broken, report);
+        }
+    }
+
+    loadable
+}
+
+/// Returns false if a condition would make building the map panic.
+fn check_conditions(
+    file: &str,
+    context: Option<(u16, &str)>,
+    number: usize,
+    portal: &PortalData,
+    index: &HashMap<String, MapIndex>,
+    broken: &HashSet<String>,
+    report: &mut Report,
+) -> bool {
+    let mut loadable = true;
+
+    for condition in portal.conditions.iter().flatten() {
+        match condition {
+            PortalConditionData::Victory(id) if id.contains(':') => {
+                if let Err(message) = resolve_area(id, index) {
+                    report.error(
+                        file,
+                        context,
+                        format!("portal {number}: victory condition {message}"),
+                    );
+                }
+            }
+            PortalConditionData::Victory(id) => {
+                if !index.contains_key(id) && !broken.contains(id) {
+                    report.error(
+                        file,
+                        context,
+                        format!("portal {number}: victory condition map '{id}' doesn't exist"),
+                    );
+                    loadable = false;
+                }
+            }
+            PortalConditionData::HeroClass(classes) => {
+                for class in classes {
+                    if try_get_hero_class(class).is_none() {
+                        report.error(
+                            file,
+                            context,
+                            format!("portal {number} requires unknown hero class '{class}'"),
+                        );
+                    }
+                }
+            }
+            PortalConditionData::MinVp(_)
+            | PortalConditionData::MinHeroes(_)
+            | PortalConditionData::BossDefeated => {}
+        }
+    }
+
+    loadable
+}
+
+fn check_area(file: &str, area: &AreaTemplate, report: &mut Report) {
+    let context = Some((area.key.order(), area.name.as_str()));
+    let bounds = Rect::new(0.0, 0.0, area.width, area.height);
+
+    let spawn = area.spawn_pos;
+
+    if !bounds.contains(spawn) {
+        report.error(
+            file,
+            context,
+            format!("spawn point ({}, {}) is outside the area", spawn.x, spawn.y),
+        );
+    } else if solid_walls(&area.inner_walls, &area.dynamic_walls).any(|wall| wall.contains(spawn)) {
+        report.error(
+            file,
+            context,
+            format!("spawn point ({}, {}) is inside a wall", spawn.x, spawn.y),
+        );
+    }
+
+    for (number, portal) in area.portals.iter().enumerate() {
+        if !portal.rect.intersects(&bounds) {
+            report.warning(
+                file,
+                context,
+                format!("portal {number} is outside the area"),
+            );
+        }
+
+        for (other_number, other) in area.portals.iter().enumerate().skip(number + 1) {
+            if portal.rect.intersects(&other.rect) {
+                report.warning(
+                    file,
+                    context,
+                    format!("portals {number} and {other_number} overlap"),
+                );
+            }
+        }
+    }
+
+    let obstacles = SpawnObstacles {
+        safe_zones: &area.safe_zones,
+        inner_walls: &area.inner_walls,
+        dynamic_walls: &area.dynamic_walls,
+    };
+
+    let mut rng = StdRng::seed_from_u64(0);
+
+    let mut check_group = |label: String, group: &EnemyGroup, region: &Rect| {
+        for (number, shape) in group.spawn_region.iter().enumerate() {
+            let shape_bounds = shape.bounding_rect();
+
+            if !shape_bounds.intersects(region) {
+                report.error(
+                    file,
+                    context,
+                    format!(
+                        "{label} has spawn region shape {number} outside of where it can spawn"
+                    ),
+                );
+            } else if shape_bounds.left() < region.left()
+                || shape_bounds.right() > region.right()
+                || shape_bounds.top() < region.top()
+                || shape_bounds.bottom() > region.bottom()
+            {
+                report.warning(
+                    file,
+                    context,
+                    format!("{label} has spawn region shape {number} partly outside of where it can spawn"),
+                );
+            }
+        }
+
+        // the largest size is the hardest to fit, so it's the one that has to fit
+        if obstacles
+            .find_position(group, region, group.size.max, &mut rng)
+            .is_none()
+        {
+            report.error(
+                file,
+                context,
+                format!(
+                    "{label} can't spawn, no spot fits an enemy of size {} outside safe zones and walls",
+                    group.size.max
+                ),
+            );
+        }
+
+        check_orbit(
+            file,
+            context,
+            &label,
+            &group.behavior,
+            group.size.max,
+            &bounds,
+            report,
+        );
+    };
+
+    for (number, group) in area.enemy_groups.iter().enumerate() {
+        check_group(format!("enemy group {number}"), group, &bounds);
+    }
+
+    for (number, enemy) in area.boss.iter().flat_map(|boss| &boss.enemies).enumerate() {
+        check_group(format!("boss enemy group {number}"), &enemy.group, &bounds);
+    }
+
+    for (number, spawner) in area.spawners.iter().enumerate() {
+        let region = spawner.region.clone().unwrap_or_else(|| bounds.clone());
+        check_group(format!("spawner {number}"), &spawner.group, &region);
+    }
+
+    for (number, enemy) in area.boss.iter().flat_map(|boss| &boss.enemies).enumerate() {
+        for (phase_number, phase) in enemy.phases.iter().enumerate() {
+            let Some(behavior) = &phase.behavior else {
+                continue;
+            };
+
+            check_orbit(
+                file,
+                context,
+                &format!("boss enemy group {number} in phase {phase_number}"),
+                behavior,
+                phase.size.unwrap_or(enemy.group.size.max),
+                &bounds,
+                report,
+            );
+        }
+    }
+}
+
+/// Orbits are moved to stay inside the area, one that's larger than the area can't be.
+fn check_orbit(
+    file: &str,
+    context: Option<(u16, &str)>,
+    label: &str,
+    behavior: &EnemyBehavior,
+    size: f32,
+    bounds: &Rect,
+    report: &mut Report,
+) {
+    if let EnemyBehavior::Orbit { radius, .. } = *behavior
+        && !orbit_fits(radius, size / 2.0, bounds)
+    {
+        report.error(
+            file,
+            context,
+            format!("{label} orbits with radius {radius}, which doesn't fit in the area"),
+        );
+    }
+}
+
+fn check_reachability(
+    spawn_map_id: Option<&str>,
+    index: &HashMap<String, MapIndex>,
+    broken: &HashSet<String>,
+    links: &Links,
+    report: &mut Report,
+) {
+    let Some(spawn_map_id) = spawn_map_id else {
+        report.error("config", None, "no spawn map is set".to_owned());
+        return;
+    };
+
+    if broken.contains(spawn_map_id) {
+        return;
+    }
+
+    let Some(spawn_map) = index.get(spawn_map_id) else {
+        report.error(
+            "config",
+            None,
+            format!("spawn map '{spawn_map_id}' is not loaded"),
+        );
+        return;
+    };
+
+    let start = AreaKey::new(spawn_map_id.to_owned(), spawn_map.start_area_order);
+
+    let mut reached = HashSet::from([start.clone()]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(key) = queue.pop_front() {
+        for (_, target) in links.get(&key).into_iter().flatten() {
+            if reached.insert(target.clone()) {
+                queue.push_back(target.clone());
+            }
+        }
+    }
+
+    let mut map_ids: Vec<&String> = index.keys().collect();
+    map_ids.sort();
+
+    for map_id in map_ids {
+        let map = &index[map_id];
+
+        let unreached: Vec<u16> = (0..map.area_count())
+            .filter(|order| !reached.contains(&AreaKey::new(map_id.clone(), *order)))
+            .collect();
+
+        if unreached.len() == map.area_names.len() {
+            report.warning(
+                &map.file,
+                None,
+                "no area of the map can be reached from the spawn area".to_owned(),
+            );
+            continue;
+        }
+
+        for order in unreached {
+            report.warning(
+                &map.file,
+                Some((order, &map.area_names[order as usize])),
+                "area can't be reached from the spawn area".to_owned(),
+            );
+        }
+    }
+}
+
+/// Checks that heroes coming through a portal don't land outside the target area or inside
+/// one of its walls.
+fn check_portal_landings(
+    file: &str,
+    area: &AreaTemplate,
+    templates: &[(String, MapTemplate)],
+    links: &Links,
+    report: &mut Report,
+) {
+    let context = Some((area.key.order(), area.name.as_str()));
+
+    for (number, key) in links.get(&area.key).into_iter().flatten() {
+        let Some(portal) = area.portals.get(*number) else {
+            continue;
+        };
+
+        let Some(target) = templates
+            .iter()
+            .find(|(_, template)| template.id == key.map_id())
+            .and_then(|(_, template)| template.try_get_area(key.order() as usize))
+        else {
+            continue;
+        };
+
+        let bounds = Rect::new(0.0, 0.0, target.width, target.height);
+
+        // heroes keeping their position arrive wherever they touched the portal,
+        // the middle of the portal stands in for all of those spots
+        let from = portal.rect.center();
+        let landing = Vec2::new(
+            portal.target_x.resolve(&bounds, from.x),
+            portal.target_y.resolve(&bounds, from.y),
+        );
+
+        let problem = if !bounds.contains(landing) {
+            "outside the area"
+        } else if solid_walls(&target.inner_walls, &target.dynamic_walls)
+            .any(|wall| wall.contains(landing))
+        {
+            "inside a wall"
+        } else {
+            continue;
+        };
+
+        report.error(
+            file,
+            context,
+            format!(
+                "portal {number} lands heroes at ({}, {}) in {} ({}), which is {problem}",
+                landing.x, landing.y, target.name, key
+            ),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PORTAL_NEXT: &str = "
+        - rect: {x: 95, y: 5, w: 5, h: 5}
+          target: Next
+          target_x: !FromLeft 5
+          target_y: Center";
+
+    const PORTAL_PREVIOUS: &str = "
+        - rect: {x: 0, y: 5, w: 5, h: 5}
+          target: Previous
+          target_x: !FromRight 5
+          target_y: Center";
+
+    fn map(id: &str, areas: &[&str]) -> (String, MapData) {
+        let areas: String = areas
+            .iter()
+            .map(|area| format!("\n  - name: test\n{area}"))
+            .collect();
+
+        let yaml = format!(
+            "id: {id}\nname: {id}\nbackground_color: '#ffffff'\ntext_color: '#000000'\nareas:{areas}"
+        );
+
+        let data = serde_yaml::from_str(&yaml).expect("fixture should parse");
+
+        (format!("{id}.yaml"), data)
+    }
+
+    fn portals(portals: &[&str]) -> String {
+        format!("    portals:{}", portals.concat())
+    }
+
+    fn check(maps: Vec<(String, MapData)>) -> Vec<MapIssue> {
+        let mut report = Report::default();
+        check_maps(maps, &HashSet::new(), Some("a"), &mut report);
+        report.issues
+    }
+
+    fn find<'a>(issues: &'a [MapIssue], message: &str) -> &'a MapIssue {
+        issues
+            .iter()
+            .find(|issue| issue.message.contains(message))
+            .unwrap_or_else(|| {
+                let issues: Vec<String> = issues.iter().map(|issue| issue.to_string()).collect();
+                panic!("no issue containing '{message}' in {issues:#?}")
+            })
+    }
+
+    #[test]
+    fn connected_maps_have_no_issues() {
+        let issues = check(vec![map(
+            "a",
+            &[&portals(&[PORTAL_NEXT]), &portals(&[PORTAL_PREVIOUS])],
+        )]);
+
+        let issues: Vec<String> = issues.iter().map(|issue| issue.to_string()).collect();
+        assert!(issues.is_empty(), "{issues:#?}");
+    }
+
+    #[test]
+    fn reports_unreachable_areas() {
+        let issues = check(vec![map(
+            "a",
+            &[
+                &portals(&[PORTAL_NEXT]),
+                &portals(&[PORTAL_PREVIOUS]),
+                &portals(&[PORTAL_PREVIOUS]),
+            ],
+        )]);
+
+        let issue = find(&issues, "can't be reached from the spawn area");
+        assert!(issue.severity == Severity::Warning);
+        assert_eq!(issue.area.as_ref().map(|(order, _)| *order), Some(2));
+    }
+
+    #[test]
+    fn reports_unreachable_maps() {
+        let issues = check(vec![
+            map("a", &["    width: 50"]),
+            map("b", &["    width: 50"]),
+        ]);
+
+        let issue = find(&issues, "no area of the map can be reached");
+        assert_eq!(issue.file, "b.yaml");
+    }
+
+    #[test]
+    fn reports_next_past_the_last_area() {
+        let issues = check(vec![map("a", &[&portals(&[PORTAL_NEXT])])]);
+
+        let issue = find(&issues, "this is the last area of the map");
+        assert!(issue.severity == Severity::Error);
+    }
+
+    #[test]
+    fn reports_previous_before_the_first_area() {
+        let issues = check(vec![map("a", &[&portals(&[PORTAL_PREVIOUS])])]);
+
+        let issue = find(&issues, "this is the first area of the map");
+        assert!(issue.severity == Severity::Error);
+
+        find(&issues, "the map can't be loaded");
+    }
+
+    #[test]
+    fn building_broken_maps_fails_instead_of_panicking() {
+        let (_, data) = map("a", &[&portals(&[PORTAL_PREVIOUS])]);
+        assert!(MapTemplate::with_known_maps(data, &|_| true).is_err());
+
+        let (_, data) = map("a", &[&portals(&[PORTAL_NEXT])]);
+        assert!(MapTemplate::with_known_maps(data, &|_| true).is_ok());
+    }
+
+    #[test]
+    fn reports_unresolvable_targets() {
+        let area = portals(&[
+            "
+        - rect: {x: 95, y: 5, w: 5, h: 5}
+          target: !Area a:missing
+          target_x: !FromLeft 5
+          target_y: Center",
+            "
+        - rect: {x: 0, y: 5, w: 5, h: 5}
+          target: !Map nowhere
+          target_x: !FromLeft 5
+          target_y: Center",
+        ]);
+
+        let issues = check(vec![map("a", &[&area])]);
+
+        assert!(find(&issues, "unknown area alias 'missing'").severity == Severity::Error);
+        assert!(find(&issues, "target map 'nowhere' doesn't exist").severity == Severity::Error);
+    }
+
+    #[test]
+    fn reports_portal_landings_in_walls() {
+        let walled = format!(
+            "{}\n    inner_walls:\n      - {{x: 0, y: 0, w: 10, h: 15}}\n    spawn_pos: {{x: 50, y: 7}}",
+            portals(&[PORTAL_PREVIOUS])
+        );
+
+        let issues = check(vec![map("a", &[&portals(&[PORTAL_NEXT]), &walled])]);
+
+        let issue = find(&issues, "which is inside a wall");
+        assert!(issue.severity == Severity::Error);
+        assert_eq!(issue.area.as_ref().map(|(order, _)| *order), Some(0));
+    }
+
+    #[test]
+    fn reports_portal_landings_outside_the_area() {
+        let far = "
+        - rect: {x: 95, y: 5, w: 5, h: 5}
+          target: Next
+          target_x: !FromLeft 150
+          target_y: Center";
+
+        let issues = check(vec![map(
+            "a",
+            &[&portals(&[far]), &portals(&[PORTAL_PREVIOUS])],
+        )]);
+
+        find(&issues, "which is outside the area");
+    }
+
+    #[test]
+    fn reports_spawn_regions_outside_the_area() {
+        let area = "
+    enemy_groups:
+      - color: '#000000'
+        count: 1
+        speed: 1
+        size: 1
+        spawn_region:
+          - {x: 200, y: 0, w: 10, h: 10}";
+
+        let issues = check(vec![map("a", &[area])]);
+
+        let issue = find(&issues, "enemy group 0 has spawn region shape 0 outside");
+        assert!(issue.severity == Severity::Error);
+    }
+
+    #[test]
+    fn reports_spawn_regions_inside_walls() {
+        let area = "
+    inner_walls:
+      - {x: 20, y: 0, w: 20, h: 15}
+    enemy_groups:
+      - color: '#000000'
+        count: 1
+        speed: 1
+        size: 1
+        spawn_region:
+          - {x: 25, y: 2, w: 10, h: 10}";
+
+        let issues = check(vec![map("a", &[area])]);
+
+        find(&issues, "enemy group 0 can't spawn");
+    }
+
+    #[test]
+    fn reports_orbits_larger_than_the_area() {
+        let area = "
+    enemy_groups:
+      - color: '#000000'
+        count: 1
+        speed: 1
+        size: 1
+        behavior: !Orbit {radius: 10}";
+
+        let issues = check(vec![map("a", &[area])]);
+
+        find(&issues, "orbits with radius 10");
+    }
+}
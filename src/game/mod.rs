@@ -1,9 +1,25 @@
+pub mod abilities;
 pub mod area;
+pub mod area_actor;
+pub mod aura;
+pub mod boss;
 pub mod components;
+pub mod dynamic_wall;
+pub mod enemy_behavior;
+pub mod enemy_spawn;
+pub mod force_zone;
 pub mod game;
+pub mod hero_class;
+pub mod hero_class_table;
 pub mod map;
 pub mod map_table;
+pub mod map_validation;
 pub mod player;
 pub mod portal;
+pub mod projectile;
+pub mod spawner;
+pub mod surface;
 pub mod systems;
+pub mod teleporter;
+pub mod timestep;
 pub mod transfer_request;
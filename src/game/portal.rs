@@ -1,7 +1,8 @@
 use super::{
     area::AreaKey,
-    components::Color,
-    map_table::{map_exists, try_get_map},
+    components::{Color, HeroProgress},
+    hero_class_table::try_get_hero_class,
+    map_table::try_get_map,
 };
 use crate::physics::rect::Rect;
 use anyhow::Result;
@@ -14,28 +15,32 @@ pub struct Portal {
     pub target: PortalTarget,
     pub target_x: PortalTargetPosX,
     pub target_y: PortalTargetPosY,
+    /// Every condition has to be met before a hero is let through.
+    pub conditions: Vec<PortalCondition>,
 }
 
 impl Portal {
-    pub fn new(data: PortalData, ctx: &PortalCreationContext) -> Self {
+    pub fn new(data: PortalData, ctx: &PortalCreationContext) -> Result<Self> {
         let target = match data.target {
-            PortalTargetData::Area(id) => {
-                let key = AreaKey::from_map_order_string(&id);
-
-                match key {
-                    Ok(key) => PortalTarget::AreaKey(key),
-                    Err(_) => PortalTarget::AreaAlias(id),
-                }
-            }
+            PortalTargetData::Area(id) => PortalTarget::from_area_id(id),
             PortalTargetData::Map(id) => {
-                if !map_exists(&id) {
-                    panic!("Map '{id}' in portal target does not exist");
+                if !(ctx.map_exists)(&id) {
+                    return Err(anyhow::anyhow!(
+                        "Map '{id}' in portal target does not exist"
+                    ));
                 }
 
                 PortalTarget::Map(id)
             }
             PortalTargetData::Previous => {
-                PortalTarget::AreaKey(AreaKey::new(ctx.map_id.clone(), ctx.area_order as u16 - 1))
+                let Some(order) = ctx.area_order.checked_sub(1) else {
+                    return Err(anyhow::anyhow!(
+                        "Portal in the first area of map '{}' targets the previous area",
+                        ctx.map_id
+                    ));
+                };
+
+                PortalTarget::AreaKey(AreaKey::new(ctx.map_id.clone(), order))
             }
             PortalTargetData::Next => {
                 PortalTarget::AreaKey(AreaKey::new(ctx.map_id.clone(), ctx.area_order as u16 + 1))
@@ -50,19 +55,159 @@ impl Portal {
             },
         };
 
-        Portal {
+        let mut conditions: Vec<PortalCondition> = data
+            .conditions
+            .unwrap_or_default()
+            .into_iter()
+            .map(|condition| PortalCondition::new(condition, ctx))
+            .collect::<Result<_>>()?;
+
+        if data.boss_exit.unwrap_or(false) {
+            conditions.push(PortalCondition::BossDefeated);
+        }
+
+        Ok(Portal {
             rect: data.rect,
             color: color.into(),
             target,
             target_x: data.target_x,
             target_y: data.target_y,
+            conditions,
+        })
+    }
+
+    pub fn is_locked(&self) -> bool {
+        !self.conditions.is_empty()
+    }
+
+    /// Returns the reason shown to the hero for the first condition it doesn't meet.
+    pub fn check(&self, ctx: &PortalCheckContext) -> Result<(), String> {
+        self.conditions
+            .iter()
+            .try_for_each(|condition| condition.check(ctx))
+    }
+}
+
+/// What a hero has to meet before a portal lets it through.
+#[derive(Clone)]
+pub enum PortalCondition {
+    MinVp(u32),
+    /// Either a single area, or any victory area of a map when only a map id is given.
+    Victory(VictoryRequirement),
+    MinHeroes(usize),
+    HeroClass(Vec<String>),
+    BossDefeated,
+}
+
+#[derive(Clone)]
+pub enum VictoryRequirement {
+    Area(PortalTarget),
+    Map(String),
+}
+
+impl PortalCondition {
+    pub fn new(data: PortalConditionData, ctx: &PortalCreationContext) -> Result<Self> {
+        let condition = match data {
+            PortalConditionData::MinVp(vp) => Self::MinVp(vp),
+            PortalConditionData::Victory(id) => match id.contains(':') {
+                true => Self::Victory(VictoryRequirement::Area(PortalTarget::from_area_id(id))),
+                false => {
+                    if !(ctx.map_exists)(&id) {
+                        return Err(anyhow::anyhow!(
+                            "Map '{id}' in portal victory condition does not exist"
+                        ));
+                    }
+
+                    Self::Victory(VictoryRequirement::Map(id))
+                }
+            },
+            PortalConditionData::MinHeroes(count) => Self::MinHeroes(count as usize),
+            PortalConditionData::HeroClass(classes) => Self::HeroClass(classes),
+            PortalConditionData::BossDefeated => Self::BossDefeated,
+        };
+
+        Ok(condition)
+    }
+
+    fn check(&self, ctx: &PortalCheckContext) -> Result<(), String> {
+        match self {
+            PortalCondition::MinVp(vp) => match ctx.progress.vp >= *vp {
+                true => Ok(()),
+                false => Err(format!("You need at least {vp} VP to enter")),
+            },
+            PortalCondition::Victory(requirement) => {
+                let (met, map_id) = match requirement {
+                    VictoryRequirement::Area(target) => match target.get_area_key() {
+                        Ok(key) => (
+                            ctx.progress.victories.contains(&key),
+                            key.map_id().to_owned(),
+                        ),
+                        Err(_) => return Err("This portal is sealed".to_owned()),
+                    },
+                    VictoryRequirement::Map(map_id) => (
+                        ctx.progress
+                            .victories
+                            .iter()
+                            .any(|key| key.map_id() == map_id),
+                        map_id.clone(),
+                    ),
+                };
+
+                let map_name = try_get_map(&map_id)
+                    .map(|map| map.name.clone())
+                    .unwrap_or(map_id);
+
+                match met {
+                    true => Ok(()),
+                    false => Err(format!("You need to complete {map_name} first")),
+                }
+            }
+            PortalCondition::MinHeroes(count) => match ctx.hero_count >= *count {
+                true => Ok(()),
+                false => Err(format!(
+                    "{count} heroes need to be here to open this portal"
+                )),
+            },
+            PortalCondition::HeroClass(classes) => {
+                if ctx
+                    .class_id
+                    .is_some_and(|id| classes.iter().any(|class| class == id))
+                {
+                    return Ok(());
+                }
+
+                let names: Vec<String> = classes
+                    .iter()
+                    .map(|id| {
+                        try_get_hero_class(id)
+                            .map(|class| class.name.clone())
+                            .unwrap_or_else(|| id.clone())
+                    })
+                    .collect();
+
+                Err(format!("Only {} can use this portal", names.join(" or ")))
+            }
+            PortalCondition::BossDefeated => match ctx.boss_completed {
+                true => Ok(()),
+                false => Err("Defeat the boss to open this portal".to_owned()),
+            },
         }
     }
 }
 
-pub struct PortalCreationContext {
+/// State of the area and the hero touching the portal, gathered once per tick.
+pub struct PortalCheckContext<'a> {
+    pub progress: &'a HeroProgress,
+    pub class_id: Option<&'a str>,
+    pub hero_count: usize,
+    pub boss_completed: bool,
+}
+
+pub struct PortalCreationContext<'a> {
     pub map_id: String,
     pub area_order: u16,
+    /// Whether a map id used as a portal target or condition can be loaded.
+    pub map_exists: &'a dyn Fn(&str) -> bool,
 }
 
 #[derive(Deserialize)]
@@ -72,6 +217,17 @@ pub struct PortalData {
     pub target: PortalTargetData,
     pub target_x: PortalTargetPosX,
     pub target_y: PortalTargetPosY,
+    pub boss_exit: Option<bool>,
+    pub conditions: Option<Vec<PortalConditionData>>,
+}
+
+#[derive(Deserialize)]
+pub enum PortalConditionData {
+    MinVp(u32),
+    Victory(String),
+    MinHeroes(u32),
+    HeroClass(Vec<String>),
+    BossDefeated,
 }
 
 #[derive(Deserialize)]
@@ -128,6 +284,14 @@ impl PortalTargetPosY {
 }
 
 impl PortalTarget {
+    /// Parses either a `map:order` key or a `map:alias` reference.
+    pub fn from_area_id(id: String) -> Self {
+        match AreaKey::from_map_order_string(&id) {
+            Ok(key) => PortalTarget::AreaKey(key),
+            Err(_) => PortalTarget::AreaAlias(id),
+        }
+    }
+
     pub fn get_area_key(&self) -> Result<AreaKey> {
         match self {
             PortalTarget::AreaKey(key) => Ok(key.clone()),
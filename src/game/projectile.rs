@@ -0,0 +1,88 @@
+use super::components::Color;
+use serde::Deserialize;
+
+#[derive(Clone)]
+pub struct ProjectileSpec {
+    pub speed: f32,
+    pub size: f32,
+    pub lifetime: f32,
+    pub color: Color,
+}
+
+#[derive(Clone, Copy)]
+pub enum ShooterKind {
+    /// Fires a single projectile at the closest hero in range.
+    Sniper { range: f32 },
+    /// Fires projectiles in evenly spaced directions, rotating the pattern after every volley.
+    Turret { directions: u32, spin: f32 },
+}
+
+/// Enemy group configuration for enemies that fire projectiles on an interval.
+#[derive(Clone)]
+pub struct ShooterConfig {
+    pub kind: ShooterKind,
+    pub interval: f32,
+    pub projectile: ProjectileSpec,
+}
+
+impl ShooterConfig {
+    pub fn new(data: ShooterData, group_color: &Color) -> Self {
+        let (kind, common) = match data {
+            ShooterData::Sniper { range, common } => (
+                ShooterKind::Sniper {
+                    range: range.unwrap_or(15.0),
+                },
+                common,
+            ),
+            ShooterData::Turret {
+                directions,
+                spin,
+                common,
+            } => (
+                ShooterKind::Turret {
+                    directions: directions.unwrap_or(4).max(1),
+                    spin: spin.unwrap_or(0.0).to_radians(),
+                },
+                common,
+            ),
+        };
+
+        Self {
+            kind,
+            interval: common.interval.unwrap_or(3.0),
+            projectile: ProjectileSpec {
+                speed: common.projectile_speed.unwrap_or(12.0),
+                size: common.projectile_size.unwrap_or(0.5),
+                lifetime: common.projectile_lifetime.unwrap_or(4.0),
+                color: common
+                    .projectile_color
+                    .map(Color::from)
+                    .unwrap_or_else(|| group_color.clone()),
+            },
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub enum ShooterData {
+    Sniper {
+        range: Option<f32>,
+        #[serde(flatten)]
+        common: ShooterCommonData,
+    },
+    Turret {
+        directions: Option<u32>,
+        spin: Option<f32>,
+        #[serde(flatten)]
+        common: ShooterCommonData,
+    },
+}
+
+#[derive(Deserialize)]
+pub struct ShooterCommonData {
+    pub interval: Option<f32>,
+    pub projectile_speed: Option<f32>,
+    pub projectile_size: Option<f32>,
+    pub projectile_lifetime: Option<f32>,
+    pub projectile_color: Option<String>,
+}
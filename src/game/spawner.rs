@@ -0,0 +1,55 @@
+use super::area::{EnemyGroup, EnemyGroupData};
+use crate::physics::rect::Rect;
+use serde::Deserialize;
+
+/// Spawns a wave of enemies from a group on an interval. The group's count is the wave size.
+#[derive(Clone)]
+pub struct SpawnerConfig {
+    pub group: EnemyGroup,
+    pub interval: f32,
+    /// Delay before the first wave.
+    pub delay: f32,
+    /// Area-relative region enemies are spawned in, the whole area if not set.
+    pub region: Option<Rect>,
+    /// Waves are cut short so no more than this many enemies from the spawner are alive at once.
+    pub max_alive: Option<u32>,
+    /// Seconds before a spawned enemy despawns. Enemies live forever if not set.
+    pub lifetime: Option<f32>,
+}
+
+impl SpawnerConfig {
+    pub fn new(data: SpawnerData) -> Self {
+        Self {
+            group: data.group.into(),
+            interval: data.interval.max(0.1),
+            delay: data.delay.unwrap_or(0.0),
+            region: data.region,
+            max_alive: data.max_alive,
+            lifetime: data.lifetime,
+        }
+    }
+}
+
+pub struct Spawner {
+    pub config: SpawnerConfig,
+    pub timer: f32,
+}
+
+impl Spawner {
+    pub fn new(config: SpawnerConfig) -> Self {
+        Self {
+            timer: config.delay,
+            config,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct SpawnerData {
+    pub group: EnemyGroupData,
+    pub interval: f32,
+    pub delay: Option<f32>,
+    pub region: Option<Rect>,
+    pub max_alive: Option<u32>,
+    pub lifetime: Option<f32>,
+}
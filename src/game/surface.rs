@@ -0,0 +1,85 @@
+use super::components::Color;
+use crate::physics::{
+    shape::{Shape, ShapeData},
+    vec2::Vec2,
+};
+use serde::Deserialize;
+
+/// Movement model where a hero's velocity eases towards its input velocity instead of
+/// snapping to it. Both rates are per second, higher values respond faster.
+#[derive(Clone, Copy)]
+pub struct Inertia {
+    /// Used while the hero is giving movement input.
+    pub acceleration: f32,
+    /// Used while the hero is not giving movement input, so low friction makes it slide.
+    pub friction: f32,
+}
+
+impl Inertia {
+    pub fn new(data: InertiaData) -> Self {
+        match data {
+            InertiaData::Ice => Self {
+                acceleration: 1.5,
+                friction: 0.3,
+            },
+            InertiaData::Mud => Self {
+                acceleration: 2.5,
+                friction: 12.0,
+            },
+            InertiaData::Custom {
+                acceleration,
+                friction,
+            } => Self {
+                acceleration,
+                friction,
+            },
+        }
+    }
+
+    pub fn apply(&self, current: Vec2, target: Vec2, delta_time: f32) -> Vec2 {
+        let rate = match target == Vec2::ZERO {
+            true => self.friction,
+            false => self.acceleration,
+        };
+
+        current + (target - current) * (rate * delta_time).min(1.0)
+    }
+}
+
+/// Area region that overrides how heroes move inside it, such as ice or mud.
+#[derive(Clone)]
+pub struct SurfaceZone {
+    pub shape: Shape,
+    pub inertia: Inertia,
+    pub color: Color,
+}
+
+impl SurfaceZone {
+    pub fn new(data: SurfaceZoneData) -> Self {
+        let default_color = match data.surface {
+            InertiaData::Ice => Color::rgba(200, 240, 255, 120),
+            InertiaData::Mud => Color::rgba(110, 80, 40, 90),
+            InertiaData::Custom { .. } => Color::rgba(0, 0, 0, 30),
+        };
+
+        Self {
+            shape: Shape::new(data.shape),
+            inertia: Inertia::new(data.surface),
+            color: data.color.map(Color::from).unwrap_or(default_color),
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Copy)]
+pub enum InertiaData {
+    Ice,
+    Mud,
+    Custom { acceleration: f32, friction: f32 },
+}
+
+#[derive(Deserialize)]
+pub struct SurfaceZoneData {
+    pub shape: ShapeData,
+    pub surface: InertiaData,
+    pub color: Option<String>,
+}
@@ -1,9 +1,23 @@
 use super::{area::Area, components::*};
 use crate::{
+    effects::{
+        Effect,
+        core_types::{EffectAction, EffectPriority},
+    },
     game::{
+        abilities::{AbilityEffects, AbilityKind, TimedAbilityEffect},
+        aura::{ActiveAura, ActiveAuras, Aura, AuraEffect},
+        boss::{BossPhase, BossState},
         components::{Direction, Position, Speed, Velocity},
-        game::PlayerStatusMessage,
+        dynamic_wall::solid_walls,
+        enemy_behavior::{self, wall_tangent},
+        force_zone::ForceKind,
+        game::{PlayerStatusMessage, PortalRefusedMessage},
         player::PlayerId,
+        portal::PortalCheckContext,
+        projectile::ShooterKind,
+        surface::Inertia,
+        teleporter::TeleportCooldown,
         transfer_request::{
             TransferRequest, TransferRequestTargetPos, TransferRequestTargetPosX,
             TransferRequestTargetPosY, TransferTarget,
@@ -12,7 +26,8 @@ use crate::{
     networking::rendering::{AreaRenderPacket, RenderNode},
     physics::vec2::Vec2,
 };
-use hecs::{With, Without};
+use hecs::{Entity, EntityBuilder, Or, With, Without};
+use std::f32::consts::{PI, TAU};
 
 pub fn system_evaluate_target_position(area: &mut Area) {
     for (_, (pos, target_pos, vel)) in area
@@ -32,78 +47,686 @@ pub fn system_commit_position(area: &mut Area) {
 pub fn system_update_velocity(area: &mut Area) {
     for (_, (vel, dir, speed)) in area
         .world
-        .query_mut::<(&mut Velocity, &Direction, &Speed)>()
+        .query_mut::<Without<(&mut Velocity, &Direction, &mut Speed), &Momentum>>()
     {
-        vel.0 = dir.0 * speed.0;
+        vel.0 = dir.0 * speed.0.get();
+    }
+
+    for (_, (pos, vel, dir, speed, momentum, inertia)) in area.world.query_mut::<(
+        &Position,
+        &mut Velocity,
+        &Direction,
+        &mut Speed,
+        &mut Momentum,
+        Option<&Inertia>,
+    )>() {
+        let target = dir.0 * speed.0.get();
+
+        // surface zones override the area surface, which overrides the hero's own inertia
+        let inertia = area
+            .surface_zones
+            .iter()
+            .find(|zone| zone.shape.contains(pos.0))
+            .map(|zone| zone.inertia)
+            .or(area.surface)
+            .or(inertia.copied());
+
+        momentum.0 = match inertia {
+            Some(inertia) => inertia.apply(momentum.0, target, area.delta_time),
+            None => target,
+        };
+
+        vel.0 = momentum.0;
     }
 }
 
+fn alive_hero_positions(area: &mut Area) -> Vec<Vec2> {
+    area.world
+        .query_mut::<Without<With<&Position, &Hero>, &Downed>>()
+        .into_iter()
+        .map(|(_, pos)| pos.0)
+        .collect()
+}
+
+fn closest_in_range(positions: &[Vec2], origin: Vec2, range: f32) -> Option<Vec2> {
+    positions
+        .iter()
+        .map(|pos| (*pos, (*pos - origin).magnitude_sq()))
+        .filter(|(_, distance_sq)| *distance_sq <= range * range)
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(pos, _)| pos)
+}
+
+pub fn system_homing(area: &mut Area) {
+    let heroes = alive_hero_positions(area);
+
+    for (_, (pos, dir, homing)) in area
+        .world
+        .query_mut::<(&Position, &mut Direction, &Homing)>()
+    {
+        let Some(target) = closest_in_range(&heroes, pos.0, homing.range) else {
+            continue;
+        };
+
+        let current_angle = dir.0.angle();
+        let target_angle = (target - pos.0).angle();
+
+        let mut diff = target_angle - current_angle;
+
+        // wrap into [-PI, PI] so the enemy always turns the short way around
+        diff = (diff + PI).rem_euclid(TAU) - PI;
+
+        let max_turn = homing.turn_speed * area.delta_time;
+
+        dir.0 = Vec2::from_polar(1.0, current_angle + diff.clamp(-max_turn, max_turn));
+    }
+}
+
+pub fn system_wall_hugging(area: &mut Area) {
+    let bounds = &area.bounds;
+
+    for (_, (pos, dir, speed, size, hugging)) in area.world.query_mut::<(
+        &Position,
+        &mut Direction,
+        &mut Speed,
+        &mut Size,
+        &WallHugging,
+    )>() {
+        let radius = size.radius();
+        let next = pos.0 + dir.0 * speed.0.get() * area.delta_time;
+
+        let blocked = next.x + radius > bounds.right()
+            || next.x - radius < bounds.left()
+            || next.y + radius > bounds.bottom()
+            || next.y - radius < bounds.top();
+
+        if blocked {
+            // turning towards the inside of the area is the same as following the
+            // tangent of the wall that is currently being faced
+            dir.0 = wall_tangent(dir.0, hugging.clockwise);
+        }
+    }
+}
+
+pub fn system_wavy(area: &mut Area) {
+    for (_, (vel, wavy)) in area.world.query_mut::<(&mut Velocity, &Wavy)>() {
+        let offset = wavy.amplitude * (TAU * wavy.frequency * area.time + wavy.phase).sin();
+
+        vel.0 = vel.0.rotated(offset);
+    }
+}
+
+pub fn system_dasher(area: &mut Area) {
+    let heroes = alive_hero_positions(area);
+
+    for (_, (pos, vel, dir, speed, dasher)) in area.world.query_mut::<(
+        &Position,
+        &mut Velocity,
+        &mut Direction,
+        &mut Speed,
+        &mut Dasher,
+    )>() {
+        dasher.timer += area.delta_time;
+
+        if dasher.dashing && dasher.timer >= dasher.dash_time {
+            dasher.dashing = false;
+            dasher.timer = 0.0;
+        } else if !dasher.dashing && dasher.timer >= dasher.rest_time {
+            dasher.dashing = true;
+            dasher.timer = 0.0;
+
+            if let Some(target) = closest_in_range(&heroes, pos.0, dasher.range) {
+                let aim = (target - pos.0).normalized();
+
+                if aim != Vec2::ZERO {
+                    dir.0 = aim;
+                }
+            }
+        }
+
+        vel.0 = match dasher.dashing {
+            true => dir.0 * speed.0.get() * dasher.dash_multiplier,
+            false => Vec2::ZERO,
+        };
+    }
+}
+
+pub fn system_stop_and_go(area: &mut Area) {
+    for (_, (vel, stop_and_go)) in area.world.query_mut::<(&mut Velocity, &mut StopAndGo)>() {
+        stop_and_go.timer += area.delta_time;
+
+        let phase_time = match stop_and_go.moving {
+            true => stop_and_go.move_time,
+            false => stop_and_go.stop_time,
+        };
+
+        if stop_and_go.timer >= phase_time {
+            stop_and_go.moving = !stop_and_go.moving;
+            stop_and_go.timer = 0.0;
+        }
+
+        if !stop_and_go.moving {
+            vel.0 = Vec2::ZERO;
+        }
+    }
+}
+
+pub fn system_orbit(area: &mut Area) {
+    if area.delta_time <= 0.0 {
+        return;
+    }
+
+    for (_, (pos, vel, speed, orbit)) in
+        area.world
+            .query_mut::<(&Position, &mut Velocity, &mut Speed, &mut Orbit)>()
+    {
+        let angular_speed = speed.0.get() / orbit.radius.max(0.01);
+
+        orbit.angle += match orbit.clockwise {
+            true => -angular_speed * area.delta_time,
+            false => angular_speed * area.delta_time,
+        };
+
+        let next = orbit.center + Vec2::from_polar(orbit.radius, orbit.angle);
+
+        vel.0 = (next - pos.0) / area.delta_time;
+    }
+}
+
+pub fn system_boss(area: &mut Area) {
+    let Some(state) = area.boss.as_ref().map(|boss| boss.state) else {
+        return;
+    };
+
+    let elapsed = match state {
+        BossState::Completed => return,
+        BossState::Waiting => {
+            let started = alive_hero_positions(area)
+                .iter()
+                .any(|pos| !area.safe_zones.iter().any(|zone| zone.contains(*pos)));
+
+            if started && let Some(boss) = &mut area.boss {
+                boss.state = BossState::Active { elapsed: 0.0 };
+            }
+
+            return;
+        }
+        BossState::Active { elapsed } => elapsed + area.delta_time,
+    };
+
+    let positions = alive_hero_positions(area);
+
+    // nobody is left to finish the fight, so let the next group start it from scratch
+    // instead of keeping the area locked with downed heroes inside
+    if positions.is_empty() {
+        if let Some(boss) = &mut area.boss {
+            boss.state = BossState::Waiting;
+        }
+
+        despawn_bosses(area);
+        area.spawn_boss_enemies();
+
+        return;
+    }
+
+    let progress = positions
+        .iter()
+        .map(|pos| (pos.x - area.bounds.left()) / area.bounds.w)
+        .fold(0.0, f32::max);
+
+    let mut phase_changes = Vec::new();
+
+    for (entity, boss) in area.world.query_mut::<&mut Boss>() {
+        while let Some(phase) = boss.phases.get(boss.next_phase)
+            && phase.trigger.is_met(elapsed, progress)
+        {
+            phase_changes.push((entity, phase.clone()));
+            boss.next_phase += 1;
+        }
+    }
+
+    for (entity, phase) in phase_changes {
+        apply_boss_phase(area, entity, &phase);
+    }
+
+    let Some(boss) = &mut area.boss else {
+        return;
+    };
+
+    if !boss.config.completion.is_met(elapsed, progress) {
+        boss.state = BossState::Active { elapsed };
+        return;
+    }
+
+    boss.state = BossState::Completed;
+
+    despawn_bosses(area);
+}
+
+fn despawn_bosses(area: &mut Area) {
+    let bosses = area
+        .world
+        .query_mut::<&Boss>()
+        .into_iter()
+        .map(|(entity, _)| entity)
+        .collect::<Vec<_>>();
+
+    for entity in bosses {
+        let _ = area.world.despawn(entity);
+    }
+}
+
+fn apply_boss_phase(area: &mut Area, entity: Entity, phase: &BossPhase) {
+    let Ok((pos, speed, size)) = area
+        .world
+        .query_one_mut::<(&Position, &mut Speed, &mut Size)>(entity)
+    else {
+        return;
+    };
+
+    if let Some(new_speed) = phase.speed {
+        speed.0.set_base(new_speed);
+    }
+
+    if let Some(new_size) = phase.size {
+        size.0.set_base(new_size);
+    }
+
+    let pos = pos.0;
+    let radius = size.radius();
+
+    let Some(behavior) = &phase.behavior else {
+        return;
+    };
+
+    enemy_behavior::clear_components(&mut area.world, entity);
+
+    let mut builder = EntityBuilder::new();
+
+    let wraps_around = area
+        .world
+        .entity(entity)
+        .is_ok_and(|entity| entity.has::<WrapAround>());
+
+    let is_free = |pos| {
+        !solid_walls(&area.inner_walls, &area.dynamic_walls)
+            .any(|wall| wall.contains_circle(pos, radius))
+    };
+
+    let pos = behavior.add_components(
+        &mut builder,
+        pos,
+        radius,
+        &area.bounds,
+        is_free,
+        &mut area.rng,
+    );
+
+    if behavior.bounces_off_bounds() && !wraps_around {
+        builder.add(BounceOffBounds);
+    }
+
+    builder.add_bundle((Position(pos), TargetPosition(pos)));
+
+    let _ = area.world.insert(entity, builder.build());
+}
+
 pub fn system_update_energy(area: &mut Area) {
-    for (_, (energy, regen, max_energy)) in
-        area.world.query_mut::<(&mut Energy, &Regen, &MaxEnergy)>()
+    for (_, (energy, regen, max_energy)) in area
+        .world
+        .query_mut::<(&mut Energy, &mut Regen, &mut MaxEnergy)>()
     {
-        energy.0 = (energy.0 + regen.0 * area.delta_time).clamp(0.0, max_energy.0);
+        energy.0 = (energy.0 + regen.0.get() * area.delta_time).clamp(0.0, max_energy.0.get());
+    }
+}
+
+pub async fn system_abilities(area: &mut Area) {
+    for (_, effects) in area.world.query_mut::<&mut AbilityEffects>() {
+        effects.tick(area.delta_time);
+    }
+
+    let mut activations = Vec::new();
+
+    for (entity, (pos, energy, abilities, downed)) in area
+        .world
+        .query_mut::<With<(&Position, &mut Energy, &mut Abilities, Option<&Downed>), &Hero>>()
+    {
+        for slot in &mut abilities.0 {
+            slot.cooldown = (slot.cooldown - area.delta_time).max(0.0);
+
+            if !slot.requested {
+                continue;
+            }
+
+            slot.requested = false;
+
+            if downed.is_some() || !slot.is_ready() || energy.0 < slot.ability.energy_cost {
+                continue;
+            }
+
+            energy.0 -= slot.ability.energy_cost;
+            slot.cooldown = slot.ability.cooldown;
 
-        if energy.0 > 90.0 {
-            energy.0 = 0.0;
+            activations.push((entity, pos.0, slot.ability.kind));
         }
     }
+
+    let priority = EffectPriority::new("ability", 0);
+
+    for (entity, origin, kind) in activations {
+        match kind {
+            AbilityKind::SpeedBoost {
+                multiplier,
+                duration,
+            } => {
+                let action = EffectAction::Multiply(multiplier);
+
+                apply_speed_effect(
+                    area,
+                    entity,
+                    "speed_boost",
+                    priority,
+                    action,
+                    false,
+                    duration,
+                );
+            }
+            AbilityKind::Invulnerability { duration } => {
+                if let Ok((invulnerable, effects)) = area
+                    .world
+                    .query_one_mut::<(&mut Invulnerable, &mut AbilityEffects)>(entity)
+                {
+                    effects.invulnerability.push(TimedAbilityEffect {
+                        remaining: duration,
+                        effect: Effect::apply_toggle(
+                            &mut vec![&mut invulnerable.0],
+                            "invulnerability",
+                            priority,
+                            EffectAction::Overwrite(true),
+                            true,
+                        ),
+                    });
+                }
+            }
+            AbilityKind::Revive { radius } => {
+                let to_revive = area
+                    .heroes
+                    .query(origin, radius)
+                    .map(|entry| entry.entity)
+                    .filter(|other| *other != entity && is_downed(area, *other))
+                    .collect::<Vec<_>>();
+
+                for other in to_revive {
+                    revive_hero(area, other).await;
+                }
+            }
+            AbilityKind::EnemySlow {
+                radius,
+                multiplier,
+                duration,
+            } => {
+                let enemies = area
+                    .enemies
+                    .query(origin, radius)
+                    .map(|entry| entry.entity)
+                    .collect::<Vec<_>>();
+
+                for enemy in enemies {
+                    // enemies only get the bookkeeping once an ability reaches them
+                    if !area
+                        .world
+                        .satisfies::<&AbilityEffects>(enemy)
+                        .unwrap_or(true)
+                    {
+                        let _ = area.world.insert_one(enemy, AbilityEffects::default());
+                    }
+
+                    let action = EffectAction::Multiply(multiplier);
+
+                    apply_speed_effect(area, enemy, "enemy_slow", priority, action, true, duration);
+                }
+            }
+        }
+    }
+}
+
+fn apply_speed_effect(
+    area: &mut Area,
+    entity: Entity,
+    id: &'static str,
+    priority: EffectPriority,
+    action: EffectAction<f32>,
+    use_receptivity: bool,
+    duration: f32,
+) {
+    if let Ok((speed, effects)) = area
+        .world
+        .query_one_mut::<(&mut Speed, &mut AbilityEffects)>(entity)
+    {
+        effects.speed.push(TimedAbilityEffect {
+            remaining: duration,
+            effect: Effect::apply_toggle(
+                &mut vec![&mut speed.0],
+                id,
+                priority,
+                action,
+                use_receptivity,
+            ),
+        });
+    }
+}
+
+fn is_downed(area: &Area, entity: Entity) -> bool {
+    area.world
+        .entity(entity)
+        .is_ok_and(|entity| entity.has::<Downed>())
+}
+
+async fn revive_hero(area: &mut Area, entity: Entity) {
+    let result = area.world.remove_one::<Downed>(entity);
+    let _ = area.world.remove_one::<DeathTimer>(entity);
+
+    if result.is_ok() {
+        let _ = area
+            .status_tx
+            .send(PlayerStatusMessage {
+                player_id: PlayerId {
+                    entity,
+                    area: area.key.clone(),
+                },
+                alive: true,
+            })
+            .await;
+    }
 }
 
 pub fn system_bounds_check(area: &mut Area) {
     for (_, (dir, pos, size)) in area
         .world
-        .query_mut::<With<(&mut Direction, &TargetPosition, &Size), &BounceOffBounds>>()
+        .query_mut::<With<(&mut Direction, &TargetPosition, &mut Size), &BounceOffBounds>>()
     {
         let bounds = &area.bounds;
+        let radius = size.radius();
 
-        if (pos.0.x + size.0 / 2.0) > bounds.right() || (pos.0.x - size.0 / 2.0) < bounds.left() {
+        if (pos.0.x + radius) > bounds.right() || (pos.0.x - radius) < bounds.left() {
             dir.0.x *= -1.0;
         }
 
-        if (pos.0.y + size.0 / 2.0) > bounds.bottom() || (pos.0.y - size.0 / 2.0) < bounds.top() {
+        if (pos.0.y + radius) > bounds.bottom() || (pos.0.y - radius) < bounds.top() {
             dir.0.y *= -1.0;
         }
     }
 
     for (_, (pos, size)) in area
         .world
-        .query_mut::<With<(&mut TargetPosition, &Size), &Bounded>>()
+        .query_mut::<With<(&mut TargetPosition, &mut Size), &Bounded>>()
     {
         let bounds = &area.bounds;
+        let radius = size.radius();
 
         // else if is allowed since the entity can only be outside the bounds in 1 direction
         // unless it's bigger than the area, in which case there's a bigger problem
-        if (pos.0.x + size.0 / 2.0) > bounds.right() {
-            pos.0.x = bounds.right() - size.0 / 2.0;
-        } else if (pos.0.x - size.0 / 2.0) < bounds.left() {
-            pos.0.x = bounds.left() + size.0 / 2.0;
+        if (pos.0.x + radius) > bounds.right() {
+            pos.0.x = bounds.right() - radius;
+        } else if (pos.0.x - radius) < bounds.left() {
+            pos.0.x = bounds.left() + radius;
         }
-        if (pos.0.y + size.0 / 2.0) > bounds.bottom() {
-            pos.0.y = bounds.bottom() - size.0 / 2.0;
-        } else if (pos.0.y - size.0 / 2.0) < bounds.top() {
-            pos.0.y = bounds.top() + size.0 / 2.0;
+        if (pos.0.y + radius) > bounds.bottom() {
+            pos.0.y = bounds.bottom() - radius;
+        } else if (pos.0.y - radius) < bounds.top() {
+            pos.0.y = bounds.top() + radius;
         }
     }
 }
 
-pub fn system_inner_wall_collision(area: &mut Area) {
-    if area.inner_walls.is_empty() {
+pub fn system_wrap_around(area: &mut Area) {
+    let bounds = &area.bounds;
+
+    for (_, (pos, target_pos)) in area
+        .world
+        .query_mut::<With<(&mut Position, &mut TargetPosition), &WrapAround>>()
+    {
+        let mut offset = Vec2::ZERO;
+
+        if target_pos.0.x > bounds.right() {
+            offset.x = -bounds.w;
+        } else if target_pos.0.x < bounds.left() {
+            offset.x = bounds.w;
+        }
+
+        if target_pos.0.y > bounds.bottom() {
+            offset.y = -bounds.h;
+        } else if target_pos.0.y < bounds.top() {
+            offset.y = bounds.h;
+        }
+
+        // moving both keeps the wall collision sweep from crossing the whole area
+        pos.0 += offset;
+        target_pos.0 += offset;
+    }
+}
+
+/// Fraction of the built up external velocity lost per second.
+const EXTERNAL_VELOCITY_DRAG: f32 = 2.0;
+
+pub fn system_force_zones(area: &mut Area) {
+    if area.force_zones.is_empty() {
         return;
     }
 
-    for (_, (pos, target_pos, size, dir, bounce, hero)) in area.world.query_mut::<With<
+    let mut started = Vec::new();
+    let mut stopped = Vec::new();
+
+    for (entity, (pos, vel, hero, external)) in area.world.query_mut::<Without<
         (
             &Position,
-            &mut TargetPosition,
-            &Size,
-            &mut Direction,
-            Option<&BounceOffBounds>,
+            &mut Velocity,
             Option<&Hero>,
+            Option<&mut ExternalVelocity>,
         ),
-        &Bounded,
+        &Downed,
     >>() {
-        let radius = size.0 / 2.0;
+        let mut acceleration = Vec2::ZERO;
+        let mut conveyed = Vec2::ZERO;
+
+        for zone in area
+            .force_zones
+            .iter()
+            .filter(|zone| hero.is_some() || zone.affects_enemies)
+            .filter(|zone| zone.shape.contains(pos.0))
+        {
+            match zone.kind {
+                ForceKind::Wind { acceleration: wind } => acceleration += wind,
+                ForceKind::Conveyor { velocity } => conveyed += velocity,
+                ForceKind::Gravity { center, strength } => {
+                    acceleration += (center - pos.0).normalized() * strength
+                }
+            }
+        }
+
+        let mut pushed = external.as_ref().map_or(Vec2::ZERO, |external| external.0);
+
+        pushed += acceleration * area.delta_time;
+        pushed *= (1.0 - EXTERNAL_VELOCITY_DRAG * area.delta_time).max(0.0);
+
+        vel.0 += pushed + conveyed;
+
+        let at_rest = acceleration == Vec2::ZERO && pushed.magnitude_sq() < 0.0001;
+
+        match external {
+            Some(_) if at_rest => stopped.push(entity),
+            Some(external) => external.0 = pushed,
+            None if !at_rest => started.push((entity, pushed)),
+            None => {}
+        }
+    }
+
+    for entity in stopped {
+        let _ = area.world.remove_one::<ExternalVelocity>(entity);
+    }
+
+    for (entity, pushed) in started {
+        let _ = area.world.insert_one(entity, ExternalVelocity(pushed));
+    }
+}
+
+pub fn system_dynamic_walls(area: &mut Area) {
+    if area.dynamic_walls.is_empty() {
+        return;
+    }
+
+    let heroes = alive_hero_positions(area);
+    let mut pressed = vec![false; area.dynamic_walls.len()];
+
+    for switch in &area.switches {
+        if heroes.iter().any(|pos| switch.shape.contains(*pos)) {
+            for door in &switch.doors {
+                pressed[*door] = true;
+            }
+        }
+    }
+
+    for (wall, pressed) in area.dynamic_walls.iter_mut().zip(pressed) {
+        wall.update(area.delta_time, pressed);
+    }
+
+    // walls moving or closing onto an entity push it out, since collision only runs for movement
+    for (_, (pos, size)) in area
+        .world
+        .query_mut::<With<(&mut Position, &mut Size), &WallBounded>>()
+    {
+        let radius = size.radius();
+
+        for wall in area.dynamic_walls.iter().filter(|wall| wall.is_solid()) {
+            if let Some((normal, penetration)) = wall.shape.penetration(pos.0, radius) {
+                pos.0 += normal * penetration;
+            }
+        }
+    }
+}
+
+pub fn system_inner_wall_collision(area: &mut Area) {
+    if area.inner_walls.is_empty() && area.dynamic_walls.is_empty() {
+        return;
+    }
+
+    let mut stopped = Vec::new();
+
+    for (entity, (pos, target_pos, size, dir, bounce, hero, projectile)) in
+        area.world.query_mut::<With<
+            (
+                &Position,
+                &mut TargetPosition,
+                &mut Size,
+                &mut Direction,
+                Option<&BounceOffBounds>,
+                Option<&Hero>,
+                Option<&Projectile>,
+            ),
+            Or<&WallBounded, &Projectile>,
+        >>()
+    {
+        let radius = size.radius();
 
         let mut current_sub_pos = pos.0;
         let total_vel = target_pos.0 - pos.0;
@@ -118,24 +741,16 @@ pub fn system_inner_wall_collision(area: &mut Area) {
 
         let sub_vel = total_vel / substeps as f32;
 
-        for _step in 0..substeps {
+        'substeps: for _step in 0..substeps {
             current_sub_pos += sub_vel;
 
-            for wall in &area.inner_walls {
-                let closest_x = current_sub_pos.x.clamp(wall.min().x, wall.max().x);
-                let closest_y = current_sub_pos.y.clamp(wall.min().y, wall.max().y);
-                let closest_point = Vec2::new(closest_x, closest_y);
-
-                let to_circle = current_sub_pos - closest_point;
-                let distance = to_circle.magnitude();
-
-                if distance < radius {
-                    let normal = if distance > 0.0001 {
-                        to_circle.normalized()
-                    } else {
-                        Vec2::UP
-                    };
-                    let penetration = radius - distance;
+            for wall in solid_walls(&area.inner_walls, &area.dynamic_walls) {
+                if let Some((normal, penetration)) = wall.penetration(current_sub_pos, radius) {
+                    // projectiles are stopped by walls instead of sliding along them
+                    if projectile.is_some() {
+                        stopped.push(entity);
+                        break 'substeps;
+                    }
 
                     current_sub_pos += normal * penetration;
 
@@ -159,6 +774,10 @@ pub fn system_inner_wall_collision(area: &mut Area) {
 
         target_pos.0 = current_sub_pos;
     }
+
+    for entity in stopped {
+        let _ = area.world.despawn(entity);
+    }
 }
 
 pub fn system_safe_zone_collision(area: &mut Area) {
@@ -166,13 +785,11 @@ pub fn system_safe_zone_collision(area: &mut Area) {
         return;
     }
 
-    for (_, (pos, target_pos, size, dir)) in
-        area.world.query_mut::<With<
-            (&Position, &mut TargetPosition, &Size, &mut Direction),
-            (&Bounded, &SafeZoneBounded),
-        >>()
-    {
-        let radius = size.0 / 2.0;
+    for (_, (pos, target_pos, size, dir)) in area.world.query_mut::<With<
+        (&Position, &mut TargetPosition, &mut Size, &mut Direction),
+        &SafeZoneBounded,
+    >>() {
+        let radius = size.radius();
 
         let mut current_sub_pos = pos.0;
         let total_vel = target_pos.0 - pos.0;
@@ -191,21 +808,7 @@ pub fn system_safe_zone_collision(area: &mut Area) {
             current_sub_pos += sub_vel;
 
             for wall in &area.safe_zones {
-                let closest_x = current_sub_pos.x.clamp(wall.min().x, wall.max().x);
-                let closest_y = current_sub_pos.y.clamp(wall.min().y, wall.max().y);
-                let closest_point = Vec2::new(closest_x, closest_y);
-
-                let to_circle = current_sub_pos - closest_point;
-                let distance = to_circle.magnitude();
-
-                if distance < radius {
-                    let normal = if distance > 0.0001 {
-                        to_circle.normalized()
-                    } else {
-                        Vec2::UP
-                    };
-                    let penetration = radius - distance;
-
+                if let Some((normal, penetration)) = wall.penetration(current_sub_pos, radius) {
                     current_sub_pos += normal * penetration;
 
                     let dot = dir.0.dot(&normal);
@@ -221,86 +824,331 @@ pub fn system_safe_zone_collision(area: &mut Area) {
     }
 }
 
+pub fn system_update_spatial_hash(area: &mut Area) {
+    area.enemies.clear();
+    area.heroes.clear();
+
+    for (entity, (pos, size, hero, enemy)) in
+        area.world
+            .query_mut::<(&Position, &mut Size, Option<&Hero>, Option<&Enemy>)>()
+    {
+        if enemy.is_some() {
+            area.enemies.insert(entity, pos.0, size.radius());
+        }
+
+        if hero.is_some() {
+            area.heroes.insert(entity, pos.0, size.radius());
+        }
+    }
+}
+
 pub async fn system_enemy_collision(area: &mut Area) {
-    let mut to_down = Vec::new();
+    let mut vulnerable = Vec::new();
+
+    for (entity, (hero_pos, hero_size, invulnerable)) in area.world.query_mut::<Without<
+        Without<With<(&Position, &mut Size, Option<&mut Invulnerable>), &Hero>, &CrossingPortal>,
+        Or<&Downed, &SpawnProtection>,
+    >>() {
+        if invulnerable.is_some_and(|invulnerable| invulnerable.0.get()) {
+            continue;
+        }
+
+        vulnerable.push((entity, hero_pos.0, hero_size.radius()));
+    }
+
+    let to_down = vulnerable
+        .into_iter()
+        .filter(|(_, pos, radius)| {
+            area.enemies.query(*pos, *radius).any(|entry| {
+                !area
+                    .world
+                    .satisfies::<&Harmless>(entry.entity)
+                    .unwrap_or(false)
+            })
+        })
+        .map(|(entity, _, _)| entity)
+        .collect::<Vec<_>>();
+
+    for entity in to_down {
+        down_hero(area, entity).await;
+    }
+}
+
+async fn down_hero(area: &mut Area, entity: Entity) {
+    if is_downed(area, entity) {
+        return;
+    }
+
+    let _ = area.world.insert_one(entity, Downed);
+
+    if let Some(duration) = area.death_timer {
+        let _ = area.world.insert_one(entity, DeathTimer(duration));
+    }
+
+    let _ = area
+        .status_tx
+        .send(PlayerStatusMessage {
+            player_id: PlayerId {
+                entity,
+                area: area.key.clone(),
+            },
+            alive: false,
+        })
+        .await;
+}
+
+pub fn system_shooters(area: &mut Area) {
+    let heroes = alive_hero_positions(area);
+    let mut shots = Vec::new();
 
-    for (entity, (hero_pos, hero_size)) in area
+    for (entity, (pos, shooter)) in area
         .world
-        .query::<Without<Without<With<(&Position, &Size), &Hero>, &CrossingPortal>, &Downed>>()
-        .iter()
+        .query_mut::<Without<(&Position, &mut Shooter), &Downed>>()
     {
-        let hero_pos = hero_pos.0;
-        let hero_size = hero_size.0;
+        shooter.timer -= area.delta_time;
 
-        for (_, (enemy_pos, enemy_size)) in area
-            .world
-            .query::<With<(&Position, &Size), &Enemy>>()
-            .iter()
-        {
-            let enemy_pos = enemy_pos.0;
-            let enemy_size = enemy_size.0;
+        if shooter.timer > 0.0 {
+            continue;
+        }
+
+        match shooter.config.kind {
+            ShooterKind::Sniper { range } => {
+                // wait with the shot until a hero comes into range
+                let Some(target) = closest_in_range(&heroes, pos.0, range) else {
+                    continue;
+                };
 
-            let distance_sq = (hero_pos - enemy_pos).magnitude_sq();
-            let radius_sum = (hero_size + enemy_size) * 0.5;
+                shots.push((entity, pos.0, (target - pos.0).normalized()));
+            }
+            ShooterKind::Turret { directions, spin } => {
+                let step = TAU / directions as f32;
+
+                for i in 0..directions {
+                    let angle = shooter.angle + step * i as f32;
+                    shots.push((entity, pos.0, Vec2::from_polar(1.0, angle)));
+                }
 
-            if distance_sq < radius_sum * radius_sum {
-                to_down.push(entity);
+                shooter.angle = (shooter.angle + spin) % TAU;
             }
         }
+
+        shooter.timer += shooter.config.interval;
+    }
+
+    for (owner, pos, direction) in shots {
+        let Ok(spec) = area
+            .world
+            .get::<&Shooter>(owner)
+            .map(|shooter| shooter.config.projectile.clone())
+        else {
+            continue;
+        };
+
+        area.spawn_projectile(owner, pos, direction, &spec);
+    }
+}
+
+pub async fn system_projectiles(area: &mut Area) {
+    let vulnerable = area
+        .world
+        .query_mut::<Without<
+            Without<With<Option<&mut Invulnerable>, &Hero>, &CrossingPortal>,
+            Or<&Downed, &SpawnProtection>,
+        >>()
+        .into_iter()
+        .filter_map(|(entity, invulnerable)| {
+            (!invulnerable.is_some_and(|invulnerable| invulnerable.0.get())).then_some(entity)
+        })
+        .collect::<Vec<_>>();
+
+    let mut to_despawn = Vec::new();
+    let mut to_down = Vec::new();
+
+    for (entity, (pos, size)) in area
+        .world
+        .query_mut::<With<(&Position, &mut Size), &Projectile>>()
+    {
+        let radius = size.radius();
+
+        let hit = area
+            .heroes
+            .query(pos.0, radius)
+            .find(|hero| vulnerable.contains(&hero.entity));
+
+        if let Some(hero) = hit {
+            to_down.push(hero.entity);
+            to_despawn.push(entity);
+        } else if !area.bounds.contains(pos.0)
+            || area
+                .safe_zones
+                .iter()
+                .any(|zone| zone.contains_circle(pos.0, radius))
+        {
+            to_despawn.push(entity);
+        }
+    }
+
+    for entity in to_despawn {
+        let _ = area.world.despawn(entity);
     }
 
     for entity in to_down {
-        let _ = area.world.insert_one(entity, Downed);
+        down_hero(area, entity).await;
+    }
+}
+
+pub fn system_spawners(area: &mut Area) {
+    for index in 0..area.spawners.len() {
+        let spawner = &mut area.spawners[index];
+
+        spawner.timer -= area.delta_time;
+
+        if spawner.timer > 0.0 {
+            continue;
+        }
+
+        spawner.timer += spawner.config.interval;
+
+        area.spawn_wave(index);
+    }
+}
+
+pub fn system_lifetime(area: &mut Area) {
+    let mut expired = Vec::new();
+
+    for (entity, lifetime) in area.world.query_mut::<&mut Lifetime>() {
+        lifetime.0 -= area.delta_time;
+
+        if lifetime.0 <= 0.0 {
+            expired.push(entity);
+        }
+    }
+
+    for entity in expired {
+        let _ = area.world.despawn(entity);
+    }
+}
+
+pub fn system_spawn_protection(area: &mut Area) {
+    let mut expired = Vec::new();
+
+    for (entity, protection) in area.world.query_mut::<&mut SpawnProtection>() {
+        protection.0 -= area.delta_time;
+
+        if protection.0 <= 0.0 {
+            expired.push(entity);
+        }
+    }
+
+    for entity in expired {
+        let _ = area.world.remove_one::<SpawnProtection>(entity);
+    }
+}
+
+pub async fn system_death_timer(area: &mut Area) {
+    let mut expired = Vec::new();
+
+    for (entity, timer) in area.world.query_mut::<With<&mut DeathTimer, &Downed>>() {
+        timer.0 -= area.delta_time;
+
+        if timer.0 <= 0.0 {
+            expired.push(entity);
+        }
+    }
+
+    for entity in expired {
+        let _ = area.world.remove::<(Downed, DeathTimer)>(entity);
+        let _ = area.world.insert_one(entity, CrossingPortal);
+
+        let player = PlayerId {
+            entity,
+            area: area.key.clone(),
+        };
 
         let _ = area
             .status_tx
             .send(PlayerStatusMessage {
-                player_id: PlayerId {
-                    entity,
-                    area: area.key.clone(),
-                },
-                alive: false,
+                player_id: player.clone(),
+                alive: true,
             })
             .await;
+
+        let req = TransferRequest {
+            player,
+            target: TransferTarget::MapStart(area.key.map_id().to_owned()),
+            target_pos: None,
+        };
+
+        let _ = area.transfer_tx.send(req);
     }
 }
 
 pub async fn system_hero_collision(area: &mut Area) {
     let mut to_revive = Vec::new();
 
-    for (_, (pos_1, size_1)) in area
+    for (entity, (pos, size)) in area
         .world
-        .query::<Without<With<(&Position, &Size), &Hero>, &Downed>>()
+        .query::<Without<With<(&Position, &mut Size), &Hero>, &Downed>>()
         .iter()
     {
-        for (entity, (pos_2, size_2)) in area
-            .world
-            .query::<With<(&Position, &Size), (&Hero, &Downed)>>()
-            .iter()
-        {
-            let distance_sq = (pos_1.0 - pos_2.0).magnitude_sq();
-            let radius_sum = (size_1.0 + size_2.0) * 0.5;
-
-            if distance_sq < radius_sum * radius_sum {
-                to_revive.push(entity);
+        for other in area.heroes.query(pos.0, size.radius()) {
+            if other.entity != entity && is_downed(area, other.entity) {
+                to_revive.push(other.entity);
             }
         }
     }
 
     for entity in to_revive {
-        let result = area.world.remove_one::<Downed>(entity);
+        revive_hero(area, entity).await;
+    }
+}
 
-        if result.is_ok() {
-            let _ = area
-                .status_tx
-                .send(PlayerStatusMessage {
-                    player_id: PlayerId {
-                        entity,
-                        area: area.key.clone(),
-                    },
-                    alive: true,
-                })
-                .await;
+pub fn system_auras(area: &mut Area) {
+    let mut in_range = Vec::new();
+
+    for (source, (pos, aura)) in area.world.query::<(&Position, &Aura)>().iter() {
+        for hero in area.heroes.query(pos.0, aura.radius) {
+            in_range.push((hero.entity, source, aura.effect));
+        }
+    }
+
+    let priority = EffectPriority::new("aura", 0);
+
+    for (entity, (active, speed, size, regen)) in area
+        .world
+        .query_mut::<With<(&mut ActiveAuras, &mut Speed, &mut Size, &mut Regen), &Hero>>()
+    {
+        active.0.retain(|active| {
+            in_range
+                .iter()
+                .any(|(hero, source, _)| *hero == entity && *source == active.source)
+        });
+
+        for (_, source, effect) in in_range.iter().filter(|(hero, ..)| *hero == entity) {
+            if active.0.iter().any(|active| active.source == *source) {
+                continue;
+            }
+
+            let (target, action) = match *effect {
+                AuraEffect::Slow { multiplier } | AuraEffect::Freeze { multiplier } => {
+                    (&mut speed.0, EffectAction::Multiply(multiplier))
+                }
+                AuraEffect::EnergyDrain { rate } => (&mut regen.0, EffectAction::Add(-rate)),
+                AuraEffect::Enlarge { multiplier } => {
+                    (&mut size.0, EffectAction::Multiply(multiplier))
+                }
+            };
+
+            active.0.push(ActiveAura {
+                source: *source,
+                effect: Effect::apply_toggle(
+                    &mut vec![target],
+                    effect.id(),
+                    priority,
+                    action,
+                    true,
+                ),
+            });
         }
     }
 }
@@ -309,75 +1157,222 @@ pub fn system_render(area: &mut Area) {
     area.render_packet = Some(AreaRenderPacket::new());
     let nodes = &mut area.render_packet.as_mut().unwrap().nodes;
 
-    for (entity, (pos, size, color, hero, enemy, downed, energy, max_energy)) in
-        area.world.query_mut::<(
-            &Position,
-            &Size,
-            &Color,
-            Option<&Hero>,
-            Option<&Enemy>,
-            Option<&Downed>,
-            Option<&Energy>,
-            Option<&MaxEnergy>,
-        )>()
-    {
+    for (
+        entity,
+        (
+            pos,
+            size,
+            color,
+            hero,
+            enemy,
+            downed,
+            death_timer,
+            protection,
+            energy,
+            max_energy,
+            abilities,
+            aura,
+        ),
+    ) in area.world.query_mut::<(
+        &Position,
+        &mut Size,
+        &Color,
+        Option<&Hero>,
+        Option<&Enemy>,
+        Option<&Downed>,
+        Option<&DeathTimer>,
+        Option<&SpawnProtection>,
+        Option<&Energy>,
+        Option<&mut MaxEnergy>,
+        Option<&Abilities>,
+        Option<&Aura>,
+    )>() {
         let mut color = color.clone();
 
         if downed.is_some() {
             color.a = 127;
         }
 
-        let energy = energy.zip(max_energy).map(|(e, m)| e.0 / m.0);
+        let energy = energy.zip(max_energy).map(|(e, m)| e.0 / m.0.get());
+
+        let ability_cooldowns = abilities
+            .map(|abilities| abilities.0.iter().map(|a| a.cooldown_fraction()).collect())
+            .unwrap_or_default();
 
         let node = RenderNode {
             x: pos.0.x,
             y: pos.0.y,
-            radius: size.0 / 2.0,
+            radius: size.radius(),
             color,
             has_border: enemy.is_some(),
             is_hero: hero.is_some(),
             downed: downed.is_some(),
+            protected: protection.is_some(),
             entity: Some(entity),
             user_id: None,
             energy,
+            ability_cooldowns,
+            aura: aura.map(|aura| (aura.radius, aura.color.clone())),
+            death_timer: death_timer.map(|timer| timer.0.max(0.0)),
         };
         nodes.push(node);
     }
 }
 
+pub fn system_teleporters(area: &mut Area) {
+    if area.teleporters.is_empty() {
+        return;
+    }
+
+    let mut arrivals = Vec::new();
+    let mut cooled_down = Vec::new();
+
+    for (entity, (pos, size, cooldown)) in area.world.query_mut::<Without<
+        With<(&mut Position, &mut Size, Option<&mut TeleportCooldown>), &Hero>,
+        &CrossingPortal,
+    >>() {
+        let radius = size.radius();
+
+        if let Some(cooldown) = cooldown {
+            cooldown.timer -= area.delta_time;
+
+            let on_arrival_pad = area.teleporters[cooldown.pad]
+                .rect
+                .contains_circle(pos.0, radius);
+
+            if cooldown.timer > 0.0 || on_arrival_pad {
+                continue;
+            }
+
+            cooled_down.push(entity);
+        }
+
+        let target = area
+            .teleporters
+            .iter()
+            .find(|pad| pad.rect.contains_circle(pos.0, radius))
+            .and_then(|pad| pad.target);
+
+        if let Some(target) = target {
+            pos.0 = area.teleporters[target].rect.center();
+            arrivals.push((entity, target));
+        }
+    }
+
+    for entity in cooled_down {
+        let _ = area.world.remove_one::<TeleportCooldown>(entity);
+    }
+
+    for (entity, pad) in arrivals {
+        let timer = area.teleporters[pad].cooldown;
+        let _ = area
+            .world
+            .insert_one(entity, TeleportCooldown { pad, timer });
+    }
+}
+
 pub async fn system_portals(area: &mut Area) {
     let mut to_cross = Vec::new();
+    let mut refused = Vec::new();
+    let mut left_refused = Vec::new();
 
-    for (entity, (pos, size)) in area
-        .world
-        .query_mut::<With<(&mut Position, &Size), &Hero>>()
-    {
-        for portal in &area.portals {
-            if portal.rect.contains_circle(pos.0, size.0 / 2.0) {
-                let area_key = portal.target.get_area_key();
-
-                if let Ok(target_area_key) = area_key {
-                    let req = TransferRequest {
-                        player: PlayerId {
-                            entity,
-                            area: area.key.clone(),
-                        },
-                        target: TransferTarget::Area(target_area_key),
-                        target_pos: Some(TransferRequestTargetPos {
-                            x: TransferRequestTargetPosX::new(portal.target_x.clone(), pos.0.x),
-                            y: TransferRequestTargetPosY::new(portal.target_y.clone(), pos.0.y),
-                        }),
-                    };
-
-                    to_cross.push(entity);
-
-                    let _ = area.transfer_tx.send(req).await;
-                }
+    let boss_completed = area.boss_completed();
+    let hero_count = area.hero_count();
+    let no_progress = HeroProgress::default();
+    let locked_areas = area.locked_areas.load_full();
+
+    for (entity, (pos, size, progress, class_id, refused_portal)) in area.world.query_mut::<Without<
+        With<
+            (
+                &mut Position,
+                &mut Size,
+                Option<&HeroProgress>,
+                Option<&HeroClassId>,
+                Option<&RefusedPortal>,
+            ),
+            &Hero,
+        >,
+        &CrossingPortal,
+    >>() {
+        let ctx = PortalCheckContext {
+            progress: progress.unwrap_or(&no_progress),
+            class_id: class_id.map(|id| id.0.as_str()),
+            hero_count,
+            boss_completed,
+        };
+
+        let radius = size.radius();
+        let mut refusal = None;
+
+        for (index, portal) in area.portals.iter().enumerate() {
+            if !portal.rect.contains_circle(pos.0, radius) {
+                continue;
+            }
+
+            if let Err(reason) = portal.check(&ctx) {
+                refusal.get_or_insert((index, reason));
+                continue;
+            }
+
+            let area_key = portal.target.get_area_key();
+
+            if let Ok(target_area_key) = &area_key
+                && *target_area_key != area.key
+                && locked_areas.contains(target_area_key)
+            {
+                refusal.get_or_insert((index, "A boss fight is going on in there".to_owned()));
+                continue;
+            }
+
+            if let Ok(target_area_key) = area_key {
+                let req = TransferRequest {
+                    player: PlayerId {
+                        entity,
+                        area: area.key.clone(),
+                    },
+                    target: TransferTarget::Area(target_area_key),
+                    target_pos: Some(TransferRequestTargetPos {
+                        x: TransferRequestTargetPosX::new(portal.target_x.clone(), pos.0.x),
+                        y: TransferRequestTargetPosY::new(portal.target_y.clone(), pos.0.y),
+                    }),
+                };
+
+                to_cross.push(entity);
+
+                let _ = area.transfer_tx.send(req);
+                break;
+            }
+        }
+
+        match refusal {
+            Some((index, reason)) if refused_portal.is_none_or(|portal| portal.0 != index) => {
+                refused.push((entity, index, reason));
             }
+            None if refused_portal.is_some() => left_refused.push(entity),
+            _ => {}
         }
     }
 
     for entity in to_cross {
         let _ = area.world.insert_one(entity, CrossingPortal);
     }
+
+    for entity in left_refused {
+        let _ = area.world.remove_one::<RefusedPortal>(entity);
+    }
+
+    for (entity, index, reason) in refused {
+        let _ = area.world.insert_one(entity, RefusedPortal(index));
+
+        let _ = area
+            .refusal_tx
+            .send(PortalRefusedMessage {
+                player_id: PlayerId {
+                    entity,
+                    area: area.key.clone(),
+                },
+                reason,
+            })
+            .await;
+    }
 }
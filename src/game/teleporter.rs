@@ -0,0 +1,75 @@
+use super::components::Color;
+use crate::{logger::Logger, physics::rect::Rect};
+use serde::Deserialize;
+
+/// Pad that instantly moves heroes to a linked pad in the same area, without a transfer request.
+#[derive(Clone)]
+pub struct Teleporter {
+    pub id: String,
+    pub rect: Rect,
+    pub color: Color,
+    /// Index of the pad heroes are sent to, or `None` for a pad that only receives.
+    pub target: Option<usize>,
+    /// Seconds a hero has to wait after arriving before it can teleport again.
+    pub cooldown: f32,
+}
+
+impl Teleporter {
+    /// Resolves pad ids into indices. Links work both ways unless marked as one-way,
+    /// in which case the target pad only receives heroes.
+    pub fn new_list(data: Vec<TeleporterData>) -> Vec<Self> {
+        let mut teleporters: Vec<Self> = data
+            .iter()
+            .map(|data| Self {
+                id: data.id.clone(),
+                rect: data.rect.clone(),
+                color: data
+                    .color
+                    .clone()
+                    .map(Color::from)
+                    .unwrap_or_else(|| Color::rgba(160, 0, 255, 60)),
+                target: None,
+                cooldown: data.cooldown.unwrap_or(1.0),
+            })
+            .collect();
+
+        for (index, data) in data.iter().enumerate() {
+            let Some(target_id) = &data.target else {
+                continue;
+            };
+
+            let Some(target) = teleporters.iter().position(|pad| pad.id == *target_id) else {
+                Logger::warn(format!(
+                    "Teleporter '{}' targets unknown teleporter '{target_id}'",
+                    data.id
+                ));
+                continue;
+            };
+
+            teleporters[index].target = Some(target);
+
+            if !data.one_way.unwrap_or(false) && teleporters[target].target.is_none() {
+                teleporters[target].target = Some(index);
+            }
+        }
+
+        teleporters
+    }
+}
+
+/// Added to a hero when it arrives on a teleporter pad. The hero can't teleport again until
+/// the cooldown has passed and it has stepped off the arrival pad, so it doesn't bounce back.
+pub struct TeleportCooldown {
+    pub pad: usize,
+    pub timer: f32,
+}
+
+#[derive(Deserialize)]
+pub struct TeleporterData {
+    pub id: String,
+    pub rect: Rect,
+    pub color: Option<String>,
+    pub target: Option<String>,
+    pub cooldown: Option<f32>,
+    pub one_way: Option<bool>,
+}
@@ -0,0 +1,130 @@
+use std::time::Duration;
+
+#[derive(Clone, Copy)]
+pub struct TimestepSettings {
+    pub step: Duration,
+    pub max_catch_up_steps: u32,
+    pub render_interval: Duration,
+}
+
+impl TimestepSettings {
+    pub fn new(simulation_framerate: f32, render_framerate: f32, max_catch_up_steps: u32) -> Self {
+        Self {
+            step: Duration::from_secs_f32(1.0 / simulation_framerate),
+            max_catch_up_steps: max_catch_up_steps.max(1),
+            render_interval: Duration::from_secs_f32(1.0 / render_framerate),
+        }
+    }
+}
+
+/// Converts real elapsed time into a whole number of fixed simulation steps.
+pub struct FixedTimestep {
+    settings: TimestepSettings,
+    accumulator: Duration,
+    since_render: Duration,
+}
+
+impl FixedTimestep {
+    pub fn new(settings: TimestepSettings) -> Self {
+        Self {
+            settings,
+            accumulator: Duration::ZERO,
+            since_render: settings.render_interval,
+        }
+    }
+
+    pub fn step(&self) -> Duration {
+        self.settings.step
+    }
+
+    /// Adds elapsed real time and returns how many steps should be simulated.
+    /// Time beyond the catch-up limit is dropped, so a long stall slows the simulation down
+    /// instead of making it run a burst of steps or a single oversized one.
+    pub fn advance(&mut self, elapsed: Duration) -> u32 {
+        self.accumulator += elapsed;
+        self.since_render += elapsed;
+
+        let mut steps = 0;
+
+        while self.accumulator >= self.settings.step {
+            if steps == self.settings.max_catch_up_steps {
+                self.accumulator = Duration::ZERO;
+                break;
+            }
+
+            self.accumulator -= self.settings.step;
+            steps += 1;
+        }
+
+        steps
+    }
+
+    /// Returns true once per render interval.
+    pub fn should_render(&mut self) -> bool {
+        if self.since_render < self.settings.render_interval {
+            return false;
+        }
+
+        self.since_render =
+            (self.since_render - self.settings.render_interval).min(self.settings.render_interval);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timestep(max_catch_up_steps: u32) -> FixedTimestep {
+        // a 125ms step and a 250ms render interval are exact in both f32 and Duration
+        FixedTimestep::new(TimestepSettings::new(8.0, 4.0, max_catch_up_steps))
+    }
+
+    #[test]
+    fn advance_carries_leftover_time() {
+        let mut timestep = timestep(5);
+
+        assert_eq!(timestep.advance(Duration::from_millis(100)), 0);
+        assert_eq!(timestep.advance(Duration::from_millis(100)), 1);
+        assert_eq!(timestep.advance(Duration::from_millis(50)), 1);
+    }
+
+    #[test]
+    fn advance_catches_up_on_missed_steps() {
+        let mut timestep = timestep(5);
+
+        assert_eq!(timestep.advance(Duration::from_millis(400)), 3);
+        assert_eq!(timestep.advance(Duration::from_millis(125)), 1);
+    }
+
+    #[test]
+    fn advance_drops_time_beyond_catch_up_limit() {
+        let mut timestep = timestep(3);
+
+        assert_eq!(timestep.advance(Duration::from_secs(10)), 3);
+        assert_eq!(timestep.advance(Duration::ZERO), 0);
+        assert_eq!(timestep.advance(Duration::from_millis(125)), 1);
+    }
+
+    #[test]
+    fn catch_up_limit_is_at_least_one_step() {
+        let mut timestep = timestep(0);
+
+        assert_eq!(timestep.advance(Duration::from_secs(1)), 1);
+    }
+
+    #[test]
+    fn renders_once_per_interval() {
+        let mut timestep = timestep(5);
+
+        assert!(timestep.should_render());
+        assert!(!timestep.should_render());
+
+        timestep.advance(Duration::from_millis(125));
+        assert!(!timestep.should_render());
+
+        timestep.advance(Duration::from_millis(125));
+        assert!(timestep.should_render());
+        assert!(!timestep.should_render());
+    }
+}
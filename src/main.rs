@@ -4,20 +4,22 @@ use evadesplus::{
     config::CONFIG,
     game::{
         game::{GameCreator, GameOutputMessage},
+        hero_class_table::get_hero_class_list,
         map_table::get_map_list,
     },
     logger::{LogCategory, Logger},
     networking::{
         chat::{Chat, ChatMessageType, ChatRequest},
         commands::{CommandRequest, handle_command},
-        helpers::create_server_announcement,
+        helpers::{create_server_announcement, create_server_notice},
         leaderboard::{Leaderboard, LeaderboardStore, LeaderboardUpdate},
         new::{
             connection_manager::{ConnectionManager, WsConnectionManager},
             handlers::{
-                client_chat_handler::ClientChatHandler, client_message_logger::ClientMessageLogger,
-                close_handler::CloseHandler, handler::ClientMessageHandler,
-                init_handler::InitHandler, move_handler::MoveHandler, ping_handler::PingHandler,
+                ability_handler::AbilityHandler, client_chat_handler::ClientChatHandler,
+                client_message_logger::ClientMessageLogger, close_handler::CloseHandler,
+                handler::ClientMessageHandler, init_handler::InitHandler,
+                move_handler::MoveHandler, ping_handler::PingHandler,
                 render_handler::RenderHandler,
             },
             server_message::{ServerMessage, ServerMessageTarget},
@@ -53,7 +55,7 @@ async fn main() -> Result<()> {
         Logger::error("Client scripts have not been compiled");
     }
 
-    let cache = Cache::new(get_map_list());
+    let cache = Cache::new(get_map_list(), get_hero_class_list());
     let cache_hash = cache.get_hash();
 
     let connection_manager = WsConnectionManager::new(SocketAddr::new(
@@ -66,6 +68,7 @@ async fn main() -> Result<()> {
         let client_message_logger = ClientMessageLogger::new(vec![
             "PING".to_owned(),
             "MOVE".to_owned(),
+            "ABIL".to_owned(),
             "CHAT".to_owned(),
         ]);
 
@@ -141,6 +144,19 @@ async fn main() -> Result<()> {
         });
     }
 
+    {
+        let mut client_rx = connection_manager.client_messages().resubscribe();
+        let ability_handler = AbilityHandler::new(user_registry.clone(), game.clone());
+
+        tokio::spawn(async move {
+            while let Ok(message) = client_rx.recv().await {
+                if ability_handler.accept_header(&message.header) {
+                    let _ = ability_handler.handle(message).await;
+                }
+            }
+        });
+    }
+
     {
         let mut client_rx = connection_manager.client_messages().resubscribe();
         let server_tx = connection_manager.server_messages().clone();
@@ -245,6 +261,7 @@ async fn main() -> Result<()> {
         let server_tx = connection_manager.server_messages().clone();
         let lb_tx = leaderboard.tx.clone();
         let chat_tx = chat.tx.clone();
+        let game = game.clone();
         let render_handler = RenderHandler {
             users: user_registry.clone(),
             server_tx: server_tx.clone(),
@@ -300,6 +317,31 @@ async fn main() -> Result<()> {
                                     }
                                 }
                             }
+
+                            if let Some(vp) = message.area_info.vp
+                                && let Some(progression) =
+                                    users.award_vp(&user_id, &message.new_id.area, vp)
+                            {
+                                let _ = lb_tx.send(LeaderboardUpdate::set_progress(
+                                    user_id.clone(),
+                                    &progression,
+                                ));
+
+                                if let Some(client_id) =
+                                    users.get(&user_id).and_then(|user| user.client_id)
+                                {
+                                    let _ = server_tx.send(progression.message(client_id)).await;
+                                }
+                            }
+
+                            if let Some(user) = users.get(&user_id) {
+                                game.send_progress_update(
+                                    message.new_id.clone(),
+                                    user.progression.vp,
+                                    user.victories,
+                                )
+                                .await;
+                            }
                         }
                     }
                     GameOutputMessage::PlayerReset(player_id) => {
@@ -307,6 +349,19 @@ async fn main() -> Result<()> {
 
                         if let Some(user_id) = users.player_to_user_id(&player_id) {
                             users.clear_victories(&user_id);
+
+                            if let Some(progression) = users.reset_run_progression(&user_id) {
+                                let _ = lb_tx.send(LeaderboardUpdate::set_progress(
+                                    user_id.clone(),
+                                    &progression,
+                                ));
+
+                                if let Some(client_id) =
+                                    users.get(&user_id).and_then(|user| user.client_id)
+                                {
+                                    let _ = server_tx.send(progression.message(client_id)).await;
+                                }
+                            }
                         }
                     }
                     GameOutputMessage::PlayerStatus(message) => {
@@ -318,6 +373,11 @@ async fn main() -> Result<()> {
                             let _ = lb_tx.send(update);
                         }
                     }
+                    GameOutputMessage::PortalRefused(message) => {
+                        if let Some(user_id) = user_registry.player_to_user_id(&message.player_id) {
+                            let _ = chat_tx.send(create_server_notice(message.reason, user_id));
+                        }
+                    }
                     GameOutputMessage::TimerUpdate(message) => {
                         let users = user_registry.clone();
 
@@ -19,3 +19,14 @@ pub fn create_server_announcement(message: String) -> ChatRequest {
         None,
     )
 }
+
+/// Server message only shown to a single user.
+pub fn create_server_notice(message: String, recipient_id: UserId) -> ChatRequest {
+    ChatRequest::new(
+        message,
+        String::new(),
+        UserId(u64::MAX),
+        ChatMessageType::ServerAnnouncement,
+        Some(vec![recipient_id]),
+    )
+}
@@ -1,6 +1,9 @@
 use tokio::sync::broadcast;
 
-use crate::{game::area::Area, networking::new::user_registry::UserId};
+use crate::{
+    game::area::Area,
+    networking::{new::user_registry::UserId, progression::Progression},
+};
 
 #[derive(Clone, Debug)]
 pub struct AreaInfo {
@@ -9,6 +12,7 @@ pub struct AreaInfo {
     order: u16,
     color: Option<String>,
     pub victory: bool,
+    pub vp: Option<u8>,
 }
 
 impl AreaInfo {
@@ -18,6 +22,7 @@ impl AreaInfo {
         order: u16,
         color: Option<String>,
         victory: bool,
+        vp: Option<u8>,
     ) -> Self {
         Self {
             map_id,
@@ -25,6 +30,7 @@ impl AreaInfo {
             order,
             color,
             victory,
+            vp,
         }
     }
 
@@ -35,6 +41,7 @@ impl AreaInfo {
             order: area.key.order(),
             color: area.text_color.clone().map(|c| c.to_hex()),
             victory: area.flags.victory,
+            vp: area.vp,
         }
     }
 
@@ -71,20 +78,34 @@ pub enum LeaderboardUpdateMode {
         player_name: String,
         downed: bool,
         area_info: AreaInfo,
+        vp: u32,
+        level: u32,
     },
     Remove,
     Transfer(AreaInfo),
     SetDowned(bool),
+    SetProgress {
+        vp: u32,
+        level: u32,
+    },
 }
 
 impl LeaderboardUpdate {
-    pub fn add(user_id: UserId, player_name: String, downed: bool, area_info: AreaInfo) -> Self {
+    pub fn add(
+        user_id: UserId,
+        player_name: String,
+        downed: bool,
+        area_info: AreaInfo,
+        progression: &Progression,
+    ) -> Self {
         Self {
             user_id,
             mode: LeaderboardUpdateMode::Add {
                 player_name,
                 downed,
                 area_info,
+                vp: progression.vp,
+                level: progression.level().0,
             },
         }
     }
@@ -110,12 +131,23 @@ impl LeaderboardUpdate {
         }
     }
 
+    pub fn set_progress(user_id: UserId, progression: &Progression) -> Self {
+        Self {
+            user_id,
+            mode: LeaderboardUpdateMode::SetProgress {
+                vp: progression.vp,
+                level: progression.level().0,
+            },
+        }
+    }
+
     pub fn header(&self) -> String {
         match &self.mode {
             LeaderboardUpdateMode::Add { .. } => "PADD",
             LeaderboardUpdateMode::Remove => "PRMV",
             LeaderboardUpdateMode::Transfer { .. } => "PTRF",
             LeaderboardUpdateMode::SetDowned(_) => "PSDN",
+            LeaderboardUpdateMode::SetProgress { .. } => "PSVP",
         }
         .to_owned()
     }
@@ -130,12 +162,17 @@ impl LeaderboardUpdate {
                 player_name,
                 downed,
                 area_info,
+                vp,
+                level,
             } => {
                 bytes.push(player_name.len().to_le_bytes()[0]); // 1 byte
                 bytes.extend_from_slice(player_name.as_bytes()); // player_name.len() bytes
                 bytes.push(*downed as u8); // 1 byte
 
                 bytes.extend_from_slice(&area_info.to_bytes());
+
+                bytes.extend_from_slice(&vp.to_le_bytes()); // 4 bytes
+                bytes.extend_from_slice(&level.to_le_bytes()); // 4 bytes
             }
             LeaderboardUpdateMode::Remove => {}
             LeaderboardUpdateMode::Transfer(area_info) => {
@@ -144,6 +181,10 @@ impl LeaderboardUpdate {
             LeaderboardUpdateMode::SetDowned(downed) => {
                 bytes.push(*downed as u8); // 1 byte
             }
+            LeaderboardUpdateMode::SetProgress { vp, level } => {
+                bytes.extend_from_slice(&vp.to_le_bytes()); // 4 bytes
+                bytes.extend_from_slice(&level.to_le_bytes()); // 4 bytes
+            }
         }
 
         bytes
@@ -156,6 +197,8 @@ struct LeaderboardStateEntry {
     player_name: String,
     area_info: AreaInfo,
     downed: bool,
+    vp: u32,
+    level: u32,
 }
 
 impl LeaderboardStateEntry {
@@ -169,6 +212,9 @@ impl LeaderboardStateEntry {
 
         bytes.extend_from_slice(&self.area_info.to_bytes());
 
+        bytes.extend_from_slice(&self.vp.to_le_bytes()); // 4 bytes
+        bytes.extend_from_slice(&self.level.to_le_bytes()); // 4 bytes
+
         bytes
     }
 }
@@ -202,17 +248,24 @@ impl LeaderboardStore {
                 player_name,
                 downed,
                 area_info,
+                vp,
+                level,
             } => self.add(LeaderboardStateEntry {
                 user_id: update.user_id,
                 player_name,
                 downed,
                 area_info,
+                vp,
+                level,
             }),
             LeaderboardUpdateMode::Transfer(area_info) => {
                 self.transfer(update.user_id, area_info);
             }
             LeaderboardUpdateMode::Remove => self.remove(update.user_id),
             LeaderboardUpdateMode::SetDowned(downed) => self.set_downed(update.user_id, downed),
+            LeaderboardUpdateMode::SetProgress { vp, level } => {
+                self.set_progress(update.user_id, vp, level)
+            }
         }
     }
 
@@ -244,6 +297,8 @@ impl LeaderboardStore {
             player_name: old_entry.player_name,
             downed: old_entry.downed,
             area_info,
+            vp: old_entry.vp,
+            level: old_entry.level,
         });
     }
 
@@ -255,6 +310,15 @@ impl LeaderboardStore {
         }
     }
 
+    fn set_progress(&mut self, user_id: UserId, vp: u32, level: u32) {
+        for entry in &mut self.state {
+            if entry.user_id == user_id {
+                entry.vp = vp;
+                entry.level = level;
+            }
+        }
+    }
+
     pub fn is_empty(&self) -> bool {
         self.state.is_empty()
     }
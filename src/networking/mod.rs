@@ -3,4 +3,5 @@ pub mod commands;
 pub mod helpers;
 pub mod leaderboard;
 pub mod new;
+pub mod progression;
 pub mod rendering;
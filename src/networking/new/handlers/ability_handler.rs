@@ -0,0 +1,41 @@
+use crate::{
+    game::game::GameHandle,
+    networking::new::{
+        client_message::ClientMessage, message_header::MessageHeader,
+        user_registry::UserRegistryHandle,
+    },
+};
+
+pub struct AbilityHandler {
+    users: UserRegistryHandle,
+    game: GameHandle,
+}
+
+impl AbilityHandler {
+    pub fn new(users: UserRegistryHandle, game: GameHandle) -> Self {
+        Self { users, game }
+    }
+}
+
+impl AbilityHandler {
+    pub fn accept_header(&self, header: &MessageHeader) -> bool {
+        header.bytes == *b"ABIL"
+    }
+
+    pub async fn handle(&self, msg: ClientMessage) -> anyhow::Result<()> {
+        let slot = *msg
+            .data
+            .first()
+            .ok_or_else(|| anyhow::anyhow!("Ability message is missing the slot index"))?;
+
+        if let Some(user_id) = self.users.client_to_user_id(msg.client_id)
+            && let Some(user) = self.users.get(&user_id)
+        {
+            self.game
+                .send_ability_request(user.player_id, slot as usize)
+                .await;
+        }
+
+        Ok(())
+    }
+}
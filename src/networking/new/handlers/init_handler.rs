@@ -3,7 +3,11 @@ use std::sync::Arc;
 use tokio::sync::{Mutex, broadcast, mpsc};
 
 use crate::{
-    game::game::GameHandle,
+    game::{
+        game::GameHandle,
+        hero_class::HeroClass,
+        hero_class_table::{get_default_hero_class, try_get_hero_class},
+    },
     networking::{
         chat::ChatRequest,
         helpers::create_server_announcement,
@@ -14,9 +18,20 @@ use crate::{
             server_message::{ServerMessage, ServerMessageTarget},
             user_registry::UserRegistryHandle,
         },
+        progression::Progression,
     },
 };
 
+/// First byte of the INIT response. Anything but `Ok` means the hero was not spawned.
+#[derive(Clone, Copy)]
+#[repr(u8)]
+enum InitStatus {
+    Ok = 0,
+    InvalidPayload = 1,
+    UnknownHeroClass = 2,
+    SpawnFailed = 3,
+}
+
 pub struct InitHandler {
     user_registry: UserRegistryHandle,
     server_tx: mpsc::Sender<ServerMessage>,
@@ -52,9 +67,21 @@ impl InitHandler {
     }
 
     pub async fn handle(&self, msg: ClientMessage) -> anyhow::Result<()> {
-        let name = String::from_utf8_lossy(&msg.data).to_string();
+        let (class, name) = match parse_payload(&msg.data) {
+            Ok(parsed) => parsed,
+            Err((status, err)) => {
+                self.send_error(&msg, status).await;
+                return Err(err);
+            }
+        };
 
-        let spawn_result = self.game.send_spawn_request().await;
+        let spawn_result = match self.game.send_spawn_request(class).await {
+            Ok(spawn_result) => spawn_result,
+            Err(err) => {
+                self.send_error(&msg, InitStatus::SpawnFailed).await;
+                return Err(err);
+            }
+        };
 
         let user_id = self.user_registry.create_user(
             name.clone(),
@@ -65,15 +92,22 @@ impl InitHandler {
         let chat_broadcast = create_server_announcement(format!("{name} joined the game"));
         let _ = self.chat_tx.send(chat_broadcast);
 
-        let lb_update =
-            LeaderboardUpdate::add(user_id.clone(), name, false, spawn_result.area_info);
+        let progression = Progression::default();
+
+        let lb_update = LeaderboardUpdate::add(
+            user_id.clone(),
+            name,
+            false,
+            spawn_result.area_info,
+            &progression,
+        );
         let _ = self.lb_tx.send(lb_update);
 
         let store = self.lb_store.try_lock().unwrap(); // FIX lol
 
         let mut bytes: Vec<u8> = Vec::new();
 
-        bytes.push(0);
+        bytes.push(InitStatus::Ok as u8);
         bytes.extend_from_slice(&user_id.0.to_le_bytes());
         bytes.extend_from_slice(&store.to_bytes());
 
@@ -94,6 +128,46 @@ impl InitHandler {
             })
             .await;
 
+        let _ = self
+            .server_tx
+            .send(progression.message(msg.client_id))
+            .await;
+
         Ok(())
     }
+
+    async fn send_error(&self, msg: &ClientMessage, status: InitStatus) {
+        let response = ServerMessage {
+            header: "INIT".into(),
+            data: vec![status as u8],
+            target: ServerMessageTarget::Single(msg.client_id),
+        };
+
+        let _ = self.server_tx.send(response).await;
+    }
+}
+
+fn parse_payload(data: &[u8]) -> Result<(&'static HeroClass, String), (InitStatus, anyhow::Error)> {
+    let invalid = |message: &str| (InitStatus::InvalidPayload, anyhow::anyhow!("{message}"));
+
+    let class_len = *data.first().ok_or_else(|| invalid("Empty INIT payload"))? as usize;
+
+    let class_id = data
+        .get(1..1 + class_len)
+        .ok_or_else(|| invalid("INIT payload is too short for its hero class"))?;
+    let class_id = String::from_utf8_lossy(class_id).to_string();
+
+    let class = match class_id.as_str() {
+        "" => get_default_hero_class(),
+        id => try_get_hero_class(id).ok_or_else(|| {
+            (
+                InitStatus::UnknownHeroClass,
+                anyhow::anyhow!("Unknown hero class '{}'", id),
+            )
+        })?,
+    };
+
+    let name = String::from_utf8_lossy(&data[1 + class_len..]).to_string();
+
+    Ok((class, name))
 }
@@ -1,3 +1,4 @@
+pub mod ability_handler;
 pub mod client_chat_handler;
 pub mod client_message_logger;
 pub mod close_handler;
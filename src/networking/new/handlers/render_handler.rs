@@ -20,10 +20,21 @@ impl RenderHandler {
             .filter(|u| u.player_id.area == message.key)
             .collect();
 
-        let message = Self::build_render_message(
-            targets,
-            message.enrich(self.users.player_to_user_id_map()).packet,
-        );
+        let message = message.enrich(self.users.player_to_user_id_map());
+
+        if let Some(delta) = message.wall_delta {
+            let wall_message = ServerMessage {
+                header: "WALL".into(),
+                data: delta,
+                target: ServerMessageTarget::Group(
+                    targets.iter().flat_map(|d| d.client_id).collect(),
+                ),
+            };
+
+            let _ = self.server_tx.send(wall_message).await;
+        }
+
+        let message = Self::build_render_message(targets, message.packet);
 
         let _ = self.server_tx.send(message).await;
     }
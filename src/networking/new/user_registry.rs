@@ -1,6 +1,6 @@
 use crate::{
     game::{area::AreaKey, player::PlayerId},
-    networking::new::client_id::ClientId,
+    networking::{new::client_id::ClientId, progression::Progression},
 };
 use arc_swap::ArcSwap;
 use std::{
@@ -24,6 +24,7 @@ pub struct UserData {
     pub joined_at: Instant,
 
     pub victories: Vec<AreaKey>,
+    pub progression: Progression,
 }
 
 static NEXT_USER_ID: AtomicU64 = AtomicU64::new(1);
@@ -77,6 +78,7 @@ impl UserRegistry {
                 name: user.name,
                 joined_at: user.joined_at,
                 victories: user.victories,
+                progression: user.progression,
             };
 
             self.add(id.clone(), new_user);
@@ -96,6 +98,18 @@ impl UserRegistry {
             data.victories.clear();
         }
     }
+
+    fn award_vp(&mut self, id: &UserId, area: &AreaKey, vp: u8) {
+        if let Some(data) = self.users.get_mut(id) {
+            data.progression.award(area, vp);
+        }
+    }
+
+    fn reset_run_progression(&mut self, id: &UserId) {
+        if let Some(data) = self.users.get_mut(id) {
+            data.progression.reset_run();
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -117,6 +131,7 @@ impl UserRegistryHandle {
             client_id: Some(client_id),
             player_id: player_id.clone(),
             victories: Vec::new(),
+            progression: Progression::default(),
         };
 
         let id = NEXT_USER_ID.fetch_add(1, Ordering::Relaxed);
@@ -174,6 +189,32 @@ impl UserRegistryHandle {
         });
     }
 
+    /// Awards the VP of an area and returns the updated progression,
+    /// or `None` if the user doesn't exist or the area was already rewarded during this run.
+    pub fn award_vp(&self, id: &UserId, area: &AreaKey, vp: u8) -> Option<Progression> {
+        if self.get(id)?.progression.has_rewarded(area) {
+            return None;
+        }
+
+        self.registry.rcu(|r| {
+            let mut new = (**r).clone();
+            new.award_vp(id, area, vp);
+            new
+        });
+
+        self.get(id).map(|user| user.progression)
+    }
+
+    pub fn reset_run_progression(&self, id: &UserId) -> Option<Progression> {
+        self.registry.rcu(|r| {
+            let mut new = (**r).clone();
+            new.reset_run_progression(id);
+            new
+        });
+
+        self.get(id).map(|user| user.progression)
+    }
+
     pub fn client_to_user_id(&self, client_id: ClientId) -> Option<UserId> {
         self.registry
             .load()
@@ -0,0 +1,88 @@
+use crate::{
+    game::area::AreaKey,
+    networking::new::{
+        client_id::ClientId,
+        server_message::{ServerMessage, ServerMessageTarget},
+    },
+};
+
+/// Experience granted for every VP earned.
+pub const EXPERIENCE_PER_VP: u32 = 10;
+
+/// VP and experience earned by a single user.
+///
+/// VP and the list of rewarded areas belong to the current run and are cleared when the hero is
+/// reset, so each area can be rewarded once per run. Experience and levels are kept for as long
+/// as the user stays connected.
+#[derive(Clone, Default)]
+pub struct Progression {
+    pub vp: u32,
+    pub experience: u32,
+    rewarded_areas: Vec<AreaKey>,
+}
+
+impl Progression {
+    /// Awards the VP of an area unless it has already been rewarded during this run.
+    /// Returns true if anything was awarded.
+    pub fn award(&mut self, area: &AreaKey, vp: u8) -> bool {
+        if self.has_rewarded(area) {
+            return false;
+        }
+
+        self.rewarded_areas.push(area.clone());
+
+        self.vp += vp as u32;
+        self.experience += vp as u32 * EXPERIENCE_PER_VP;
+
+        true
+    }
+
+    pub fn has_rewarded(&self, area: &AreaKey) -> bool {
+        self.rewarded_areas.contains(area)
+    }
+
+    pub fn reset_run(&mut self) {
+        self.vp = 0;
+        self.rewarded_areas.clear();
+    }
+
+    /// Returns the current level, the experience gained towards the next level,
+    /// and the experience needed to reach it. Each level costs 100 experience more than the last.
+    pub fn level(&self) -> (u32, u32, u32) {
+        let mut level = 1;
+        let mut remaining = self.experience;
+
+        while remaining >= experience_for_level(level) {
+            remaining -= experience_for_level(level);
+            level += 1;
+        }
+
+        (level, remaining, experience_for_level(level))
+    }
+
+    pub fn message(&self, client_id: ClientId) -> ServerMessage {
+        ServerMessage {
+            header: "PROG".into(),
+            data: self.to_bytes(),
+            target: ServerMessageTarget::Single(client_id),
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let (level, level_experience, level_requirement) = self.level();
+
+        let mut bytes = Vec::new();
+
+        bytes.extend_from_slice(&self.vp.to_le_bytes());
+        bytes.extend_from_slice(&self.experience.to_le_bytes());
+        bytes.extend_from_slice(&level.to_le_bytes());
+        bytes.extend_from_slice(&level_experience.to_le_bytes());
+        bytes.extend_from_slice(&level_requirement.to_le_bytes());
+
+        bytes
+    }
+}
+
+fn experience_for_level(level: u32) -> u32 {
+    level * 100
+}
@@ -11,6 +11,8 @@ use crate::{
 pub struct AreaRenderMessage {
     pub key: AreaKey,
     pub packet: AreaRenderPacket,
+    /// Dynamic walls that changed since the previous render, sent to clients as a `WALL` message.
+    pub wall_delta: Option<Vec<u8>>,
 }
 
 impl AreaRenderMessage {
@@ -36,9 +38,13 @@ impl AreaRenderMessage {
                         has_border: n.has_border,
                         is_hero: n.is_hero,
                         downed: n.downed,
+                        protected: n.protected,
                         entity: n.entity,
                         user_id: map.get(&player_id).cloned(),
                         energy: n.energy,
+                        ability_cooldowns: n.ability_cooldowns,
+                        aura: n.aura,
+                        death_timer: n.death_timer,
                     }
                 } else {
                     n
@@ -49,6 +55,7 @@ impl AreaRenderMessage {
         return AreaRenderMessage {
             key: self.key,
             packet: AreaRenderPacket { nodes },
+            wall_delta: self.wall_delta,
         };
     }
 }
@@ -85,9 +92,13 @@ pub struct RenderNode {
     pub has_border: bool,
     pub is_hero: bool,
     pub downed: bool,
+    pub protected: bool,
     pub entity: Option<Entity>,
     pub user_id: Option<UserId>,
     pub energy: Option<f32>,
+    pub ability_cooldowns: Vec<f32>,
+    pub aura: Option<(f32, Color)>,
+    pub death_timer: Option<f32>,
 }
 
 impl RenderNode {
@@ -99,11 +110,18 @@ impl RenderNode {
         bytes.extend_from_slice(&self.color.to_bytes());
 
         let has_energy = self.energy.is_some();
+        let has_abilities = !self.ability_cooldowns.is_empty();
+        let has_aura = self.aura.is_some();
+        let has_death_timer = self.death_timer.is_some();
 
         let flags = (self.has_border as u8)
             | (self.is_hero as u8) << 1
             | (self.downed as u8) << 2
-            | (has_energy as u8) << 3;
+            | (has_energy as u8) << 3
+            | (has_abilities as u8) << 4
+            | (has_aura as u8) << 5
+            | (has_death_timer as u8) << 6
+            | (self.protected as u8) << 7;
 
         bytes.push(flags);
 
@@ -117,6 +135,23 @@ impl RenderNode {
             bytes.extend_from_slice(&energy.to_le_bytes());
         }
 
+        if has_abilities {
+            bytes.push(self.ability_cooldowns.len() as u8);
+
+            for cooldown in &self.ability_cooldowns {
+                bytes.extend_from_slice(&cooldown.to_le_bytes());
+            }
+        }
+
+        if let Some((radius, color)) = &self.aura {
+            bytes.extend_from_slice(&radius.to_le_bytes());
+            bytes.extend_from_slice(&color.to_bytes());
+        }
+
+        if let Some(death_timer) = self.death_timer {
+            bytes.extend_from_slice(&death_timer.to_le_bytes());
+        }
+
         bytes
     }
 
@@ -1,4 +1,4 @@
-use crate::game::map::MapData;
+use crate::game::{hero_class::HeroClassData, map::MapData};
 use anyhow::Result;
 
 pub fn parse_map(path: &str) -> Result<MapData> {
@@ -8,3 +8,11 @@ pub fn parse_map(path: &str) -> Result<MapData> {
 
     Ok(map)
 }
+
+pub fn parse_hero_classes(path: &str) -> Result<Vec<HeroClassData>> {
+    let file = std::fs::read_to_string(path)?;
+
+    let classes: Vec<HeroClassData> = serde_yaml::from_str(&file)?;
+
+    Ok(classes)
+}
@@ -1,2 +1,4 @@
 pub mod rect;
+pub mod shape;
+pub mod spatial_hash;
 pub mod vec2;
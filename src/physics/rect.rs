@@ -1,4 +1,5 @@
 use super::vec2::Vec2;
+use rand::Rng;
 use serde::Deserialize;
 
 #[derive(Debug, Clone, Deserialize)]
@@ -73,9 +74,9 @@ impl Rect {
             && self.y + self.h > other.y
     }
 
-    pub fn random_inside(&self) -> Vec2 {
-        let x = self.x + self.w * (rand::random::<f32>());
-        let y = self.y + self.h * (rand::random::<f32>());
+    pub fn random_inside(&self, rng: &mut impl Rng) -> Vec2 {
+        let x = self.x + self.w * rng.random::<f32>();
+        let y = self.y + self.h * rng.random::<f32>();
         Vec2::new(x, y)
     }
 
@@ -0,0 +1,561 @@
+use super::{rect::Rect, vec2::Vec2};
+use serde::{
+    Deserialize, Deserializer,
+    de::{
+        EnumAccess, Error, MapAccess, Visitor,
+        value::{EnumAccessDeserializer, MapAccessDeserializer},
+    },
+};
+use std::{f32::consts::TAU, fmt};
+
+/// Static area geometry used for inner walls and safe zones.
+#[derive(Debug, Clone)]
+pub enum Shape {
+    Rect(Rect),
+    Circle {
+        center: Vec2,
+        radius: f32,
+    },
+    /// Convex polygon. Points can be given in either winding order.
+    Polygon {
+        points: Vec<Vec2>,
+    },
+    /// Rectangle rotated around its center by `angle` radians.
+    RotatedRect {
+        center: Vec2,
+        half_size: Vec2,
+        angle: f32,
+    },
+}
+
+impl Shape {
+    pub fn new(data: ShapeData) -> Self {
+        match data {
+            ShapeData::Rect(rect) | ShapeData::Tagged(TaggedShapeData::Rect(rect)) => {
+                Self::Rect(rect)
+            }
+            ShapeData::Tagged(TaggedShapeData::Circle { x, y, radius }) => Self::Circle {
+                center: Vec2::new(x, y),
+                radius,
+            },
+            ShapeData::Tagged(TaggedShapeData::Polygon(points)) => Self::Polygon { points },
+            ShapeData::Tagged(TaggedShapeData::RotatedRect { x, y, w, h, angle }) => {
+                let rect = Rect::new(x, y, w, h);
+
+                Self::RotatedRect {
+                    center: rect.center(),
+                    half_size: Vec2::new(w / 2.0, h / 2.0),
+                    angle: angle.to_radians(),
+                }
+            }
+        }
+    }
+
+    pub fn contains(&self, point: Vec2) -> bool {
+        match self {
+            Self::Rect(rect) => rect.contains(point),
+            Self::Circle { center, radius } => (point - *center).magnitude_sq() < radius * radius,
+            Self::Polygon { points } => {
+                let winding = winding(points);
+
+                edges(points).all(|(a, b)| (point - a).dot(&outward_normal(a, b, winding)) <= 0.0)
+            }
+            Self::RotatedRect {
+                center,
+                half_size,
+                angle,
+            } => {
+                let local = (point - *center).rotated(-angle);
+                local.x.abs() <= half_size.x && local.y.abs() <= half_size.y
+            }
+        }
+    }
+
+    pub fn contains_circle(&self, center: Vec2, radius: f32) -> bool {
+        match self {
+            Self::Rect(rect) => rect.contains_circle(center, radius),
+            _ => self.penetration(center, radius).is_some(),
+        }
+    }
+
+    /// Returns the direction and distance a circle has to move to stop overlapping the shape,
+    /// or `None` if they don't overlap.
+    pub fn penetration(&self, center: Vec2, radius: f32) -> Option<(Vec2, f32)> {
+        match self {
+            Self::Rect(rect) => aabb_penetration(rect.min(), rect.max(), center, radius),
+            Self::Circle {
+                center: shape_center,
+                radius: shape_radius,
+            } => {
+                let to_circle = center - *shape_center;
+                let distance = to_circle.magnitude();
+                let radius_sum = radius + shape_radius;
+
+                if distance >= radius_sum {
+                    return None;
+                }
+
+                let normal = if distance > 0.0001 {
+                    to_circle / distance
+                } else {
+                    Vec2::UP
+                };
+
+                Some((normal, radius_sum - distance))
+            }
+            Self::Polygon { points } => polygon_penetration(points, center, radius),
+            Self::RotatedRect {
+                center: rect_center,
+                half_size,
+                angle,
+            } => {
+                let local = (center - *rect_center).rotated(-angle);
+
+                aabb_penetration(-*half_size, *half_size, local, radius)
+                    .map(|(normal, depth)| (normal.rotated(*angle), depth))
+            }
+        }
+    }
+
+    /// Center of the shape. For polygons this is the average of the points.
+    pub fn center(&self) -> Vec2 {
+        match self {
+            Self::Rect(rect) => rect.center(),
+            Self::Circle { center, .. } | Self::RotatedRect { center, .. } => *center,
+            Self::Polygon { points } => {
+                points.iter().fold(Vec2::ZERO, |sum, point| sum + *point)
+                    / points.len().max(1) as f32
+            }
+        }
+    }
+
+    /// Smallest axis-aligned rectangle containing the whole shape.
+    pub fn bounding_rect(&self) -> Rect {
+        match self {
+            Self::Rect(rect) => rect.clone(),
+            Self::Circle { center, radius } => Rect::new(
+                center.x - radius,
+                center.y - radius,
+                radius * 2.0,
+                radius * 2.0,
+            ),
+            Self::Polygon { points } => {
+                let min = points
+                    .iter()
+                    .fold(Vec2::new(f32::MAX, f32::MAX), |min, point| {
+                        Vec2::new(min.x.min(point.x), min.y.min(point.y))
+                    });
+                let max = points
+                    .iter()
+                    .fold(Vec2::new(f32::MIN, f32::MIN), |max, point| {
+                        Vec2::new(max.x.max(point.x), max.y.max(point.y))
+                    });
+
+                Rect::new(min.x, min.y, max.x - min.x, max.y - min.y)
+            }
+            Self::RotatedRect {
+                center,
+                half_size,
+                angle,
+            } => {
+                let (sin, cos) = angle.sin_cos();
+                let extent_x = (half_size.x * cos).abs() + (half_size.y * sin).abs();
+                let extent_y = (half_size.x * sin).abs() + (half_size.y * cos).abs();
+
+                Rect::new(
+                    center.x - extent_x,
+                    center.y - extent_y,
+                    extent_x * 2.0,
+                    extent_y * 2.0,
+                )
+            }
+        }
+    }
+
+    pub fn translated(&self, offset: Vec2) -> Self {
+        match self {
+            Self::Rect(rect) => Self::Rect(Rect::new(
+                rect.x + offset.x,
+                rect.y + offset.y,
+                rect.w,
+                rect.h,
+            )),
+            Self::Circle { center, radius } => Self::Circle {
+                center: *center + offset,
+                radius: *radius,
+            },
+            Self::Polygon { points } => Self::Polygon {
+                points: points.iter().map(|point| *point + offset).collect(),
+            },
+            Self::RotatedRect {
+                center,
+                half_size,
+                angle,
+            } => Self::RotatedRect {
+                center: *center + offset,
+                half_size: *half_size,
+                angle: *angle,
+            },
+        }
+    }
+
+    /// Outline points of polygonal shapes in world space.
+    fn outline(&self) -> Vec<Vec2> {
+        match self {
+            Self::Polygon { points } => points.clone(),
+            Self::RotatedRect {
+                center,
+                half_size,
+                angle,
+            } => [
+                Vec2::new(-half_size.x, -half_size.y),
+                Vec2::new(half_size.x, -half_size.y),
+                Vec2::new(half_size.x, half_size.y),
+                Vec2::new(-half_size.x, half_size.y),
+            ]
+            .into_iter()
+            .map(|corner| *center + corner.rotated(*angle))
+            .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Serialized as a type byte followed by the shape data.
+    /// Rotated rectangles are sent as polygons, so clients only need to draw three kinds of shapes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+
+        match self {
+            Self::Rect(rect) => {
+                bytes.push(0);
+                bytes.extend_from_slice(&rect.to_bytes());
+            }
+            Self::Circle { center, radius } => {
+                bytes.push(1);
+                bytes.extend_from_slice(&center.x.to_le_bytes());
+                bytes.extend_from_slice(&center.y.to_le_bytes());
+                bytes.extend_from_slice(&radius.to_le_bytes());
+            }
+            Self::Polygon { .. } | Self::RotatedRect { .. } => {
+                let points = self.outline();
+
+                bytes.push(2);
+                bytes.extend_from_slice(&(points.len() as u16).to_le_bytes());
+
+                for point in points {
+                    bytes.extend_from_slice(&point.x.to_le_bytes());
+                    bytes.extend_from_slice(&point.y.to_le_bytes());
+                }
+            }
+        }
+
+        bytes
+    }
+}
+
+fn aabb_penetration(min: Vec2, max: Vec2, center: Vec2, radius: f32) -> Option<(Vec2, f32)> {
+    let closest = center.clamp(min, max);
+    let to_circle = center - closest;
+    let distance = to_circle.magnitude();
+
+    if distance > 0.0001 {
+        return (distance < radius).then(|| (to_circle / distance, radius - distance));
+    }
+
+    // the center is inside, so push the circle out through the closest edge
+    [
+        (center.x - min.x, Vec2::LEFT),
+        (max.x - center.x, Vec2::RIGHT),
+        (center.y - min.y, Vec2::DOWN),
+        (max.y - center.y, Vec2::UP),
+    ]
+    .into_iter()
+    .min_by(|a, b| a.0.total_cmp(&b.0))
+    .map(|(depth, normal)| (normal, radius + depth))
+}
+
+fn polygon_penetration(points: &[Vec2], center: Vec2, radius: f32) -> Option<(Vec2, f32)> {
+    let winding = winding(points);
+
+    let mut inside = true;
+    let mut closest: Option<(f32, Vec2, Vec2)> = None;
+
+    for (a, b) in edges(points) {
+        let normal = outward_normal(a, b, winding);
+
+        if (center - a).dot(&normal) > 0.0 {
+            inside = false;
+        }
+
+        let edge = b - a;
+        let t = ((center - a).dot(&edge) / edge.magnitude_sq().max(0.0001)).clamp(0.0, 1.0);
+        let point = a + edge * t;
+        let distance_sq = (center - point).magnitude_sq();
+
+        if closest.is_none_or(|(closest_sq, ..)| distance_sq < closest_sq) {
+            closest = Some((distance_sq, point, normal));
+        }
+    }
+
+    let (distance_sq, point, edge_normal) = closest?;
+    let distance = distance_sq.sqrt();
+
+    if inside {
+        return Some((edge_normal, radius + distance));
+    }
+
+    if distance >= radius {
+        return None;
+    }
+
+    let normal = if distance > 0.0001 {
+        (center - point) / distance
+    } else {
+        edge_normal
+    };
+
+    Some((normal, radius - distance))
+}
+
+fn edges(points: &[Vec2]) -> impl Iterator<Item = (Vec2, Vec2)> + '_ {
+    points
+        .iter()
+        .zip(points.iter().cycle().skip(1))
+        .map(|(a, b)| (*a, *b))
+}
+
+/// Sign of the polygon's signed area, positive for counter-clockwise points.
+fn winding(points: &[Vec2]) -> f32 {
+    let area: f32 = edges(points).map(|(a, b)| a.x * b.y - b.x * a.y).sum();
+    area.signum()
+}
+
+/// Collision only works for convex polygons with a real area, anything else would
+/// collide everywhere or in the wrong places.
+fn check_polygon(points: &[Vec2]) -> Result<(), String> {
+    if points.len() < 3 {
+        return Err(format!(
+            "polygon needs at least 3 points, got {}",
+            points.len()
+        ));
+    }
+
+    if edges(points).any(|(a, b)| a == b) {
+        return Err("polygon has the same point twice in a row".to_owned());
+    }
+
+    let area: f32 = edges(points).map(|(a, b)| a.x * b.y - b.x * a.y).sum();
+
+    if area.abs() < f32::EPSILON {
+        return Err("polygon has no area".to_owned());
+    }
+
+    let turns: Vec<f32> = (0..points.len())
+        .map(|i| {
+            let a = points[i];
+            let b = points[(i + 1) % points.len()];
+            let c = points[(i + 2) % points.len()];
+
+            let (first, second) = (b - a, c - b);
+            let cross = first.x * second.y - first.y * second.x;
+
+            cross.atan2(first.dot(&second))
+        })
+        .collect();
+
+    if turns.iter().any(|turn| *turn > 0.0) && turns.iter().any(|turn| *turn < 0.0) {
+        return Err("polygon is not convex".to_owned());
+    }
+
+    // a convex outline turns around exactly once, a star shape turns around more often
+    let total: f32 = turns.iter().sum();
+
+    if (total.abs() - TAU).abs() > 0.01 {
+        return Err("polygon is not convex".to_owned());
+    }
+
+    Ok(())
+}
+
+fn outward_normal(a: Vec2, b: Vec2, winding: f32) -> Vec2 {
+    let edge = b - a;
+    (Vec2::new(edge.y, -edge.x) * winding).normalized()
+}
+
+/// Plain `{x, y, w, h}` maps are read as rectangles so existing maps keep working,
+/// other shapes are written as tagged values like `!Circle {x: 5, y: 5, radius: 2}`.
+pub enum ShapeData {
+    Rect(Rect),
+    Tagged(TaggedShapeData),
+}
+
+impl<'de> Deserialize<'de> for ShapeData {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ShapeVisitor;
+
+        impl<'de> Visitor<'de> for ShapeVisitor {
+            type Value = ShapeData;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a rectangle or a tagged shape")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+                Rect::deserialize(MapAccessDeserializer::new(map)).map(ShapeData::Rect)
+            }
+
+            fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Self::Value, A::Error> {
+                let shape = TaggedShapeData::deserialize(EnumAccessDeserializer::new(data))?;
+
+                if let TaggedShapeData::Polygon(points) = &shape {
+                    check_polygon(points).map_err(A::Error::custom)?;
+                }
+
+                Ok(ShapeData::Tagged(shape))
+            }
+        }
+
+        deserializer.deserialize_any(ShapeVisitor)
+    }
+}
+
+#[derive(Deserialize)]
+pub enum TaggedShapeData {
+    Rect(Rect),
+    Circle {
+        x: f32,
+        y: f32,
+        radius: f32,
+    },
+    Polygon(Vec<Vec2>),
+    /// Angle in degrees, rotating the rectangle around its center.
+    RotatedRect {
+        x: f32,
+        y: f32,
+        w: f32,
+        h: f32,
+        angle: f32,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn circle() -> Shape {
+        Shape::Circle {
+            center: Vec2::new(5.0, 5.0),
+            radius: 2.0,
+        }
+    }
+
+    fn rect() -> Shape {
+        Shape::Rect(Rect::new(0.0, 0.0, 4.0, 2.0))
+    }
+
+    fn triangle() -> Shape {
+        Shape::Polygon {
+            points: vec![
+                Vec2::new(0.0, 0.0),
+                Vec2::new(4.0, 0.0),
+                Vec2::new(0.0, 4.0),
+            ],
+        }
+    }
+
+    fn assert_penetration(shape: &Shape, center: Vec2, radius: f32, normal: Vec2, depth: f32) {
+        let (got_normal, got_depth) = shape
+            .penetration(center, radius)
+            .expect("circle should overlap the shape");
+
+        assert!(
+            (got_normal - normal).magnitude() < 1e-4,
+            "{got_normal} != {normal}"
+        );
+        assert!((got_depth - depth).abs() < 1e-4, "{got_depth} != {depth}");
+    }
+
+    fn polygon(yaml: &str) -> Result<ShapeData, serde_yaml::Error> {
+        serde_yaml::from_str(&format!("!Polygon {yaml}"))
+    }
+
+    #[test]
+    fn circle_contains_and_penetration() {
+        let shape = circle();
+
+        assert!(shape.contains(Vec2::new(6.0, 5.0)));
+        assert!(!shape.contains(Vec2::new(7.5, 5.0)));
+
+        assert_penetration(&shape, Vec2::new(7.5, 5.0), 1.0, Vec2::RIGHT, 0.5);
+        assert!(shape.penetration(Vec2::new(8.0, 5.0), 1.0).is_none());
+    }
+
+    #[test]
+    fn rect_contains_and_penetration() {
+        let shape = rect();
+
+        assert!(shape.contains(Vec2::new(1.0, 1.0)));
+        assert!(!shape.contains(Vec2::new(5.0, 1.0)));
+
+        assert_penetration(&shape, Vec2::new(4.5, 1.0), 1.0, Vec2::RIGHT, 0.5);
+        assert_penetration(&shape, Vec2::new(3.5, 1.0), 1.0, Vec2::RIGHT, 1.5);
+        assert!(shape.penetration(Vec2::new(5.0, 1.0), 1.0).is_none());
+    }
+
+    #[test]
+    fn polygon_contains_and_penetration() {
+        let shape = triangle();
+
+        assert!(shape.contains(Vec2::new(1.0, 1.0)));
+        assert!(!shape.contains(Vec2::new(3.0, 3.0)));
+
+        assert_penetration(&shape, Vec2::new(1.0, -0.5), 1.0, Vec2::DOWN, 0.5);
+        assert_penetration(&shape, Vec2::new(2.0, 0.5), 1.0, Vec2::DOWN, 1.5);
+        assert!(shape.penetration(Vec2::new(4.0, 4.0), 1.0).is_none());
+    }
+
+    #[test]
+    fn polygon_winding_does_not_matter() {
+        let shape = Shape::Polygon {
+            points: vec![
+                Vec2::new(0.0, 0.0),
+                Vec2::new(0.0, 4.0),
+                Vec2::new(4.0, 0.0),
+            ],
+        };
+
+        assert!(shape.contains(Vec2::new(1.0, 1.0)));
+        assert!(!shape.contains(Vec2::new(3.0, 3.0)));
+        assert_penetration(&shape, Vec2::new(1.0, -0.5), 1.0, Vec2::DOWN, 0.5);
+    }
+
+    #[test]
+    fn accepts_convex_polygons() {
+        assert!(polygon("[{x: 0, y: 0}, {x: 4, y: 0}, {x: 0, y: 4}]").is_ok());
+        assert!(polygon("[{x: 0, y: 0}, {x: 0, y: 4}, {x: 4, y: 4}, {x: 4, y: 0}]").is_ok());
+        assert!(polygon("[{x: 0, y: 0}, {x: 2, y: 0}, {x: 4, y: 0}, {x: 4, y: 4}]").is_ok());
+    }
+
+    #[test]
+    fn rejects_polygons_with_too_few_points() {
+        assert!(polygon("[]").is_err());
+        assert!(polygon("[{x: 1, y: 1}]").is_err());
+        assert!(polygon("[{x: 0, y: 0}, {x: 4, y: 0}]").is_err());
+    }
+
+    #[test]
+    fn rejects_degenerate_polygons() {
+        assert!(polygon("[{x: 0, y: 0}, {x: 1, y: 1}, {x: 2, y: 2}]").is_err());
+        assert!(polygon("[{x: 0, y: 0}, {x: 0, y: 0}, {x: 0, y: 0}]").is_err());
+        assert!(polygon("[{x: 0, y: 0}, {x: 4, y: 0}, {x: 4, y: 0}, {x: 0, y: 4}]").is_err());
+    }
+
+    #[test]
+    fn rejects_concave_polygons() {
+        assert!(polygon("[{x: 0, y: 0}, {x: 4, y: 0}, {x: 1, y: 1}, {x: 0, y: 4}]").is_err());
+        assert!(
+            polygon("[{x: 0, y: 0}, {x: 2, y: 4}, {x: 4, y: 0}, {x: 0, y: 3}, {x: 4, y: 3}]")
+                .is_err()
+        );
+    }
+}
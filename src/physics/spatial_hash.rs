@@ -0,0 +1,191 @@
+use super::{rect::Rect, vec2::Vec2};
+use hecs::Entity;
+
+#[derive(Clone, Copy)]
+pub struct SpatialEntry {
+    pub entity: Entity,
+    pub pos: Vec2,
+    pub radius: f32,
+}
+
+/// Uniform grid over a fixed rectangle. Entries are stored in every cell their circle overlaps,
+/// so queries only have to look at the cells covered by the queried circle.
+pub struct SpatialHash {
+    origin: Vec2,
+    cell_size: f32,
+    columns: usize,
+    rows: usize,
+    cells: Vec<Vec<u32>>,
+    entries: Vec<SpatialEntry>,
+}
+
+impl SpatialHash {
+    pub fn new(bounds: &Rect, cell_size: f32) -> Self {
+        let cell_size = cell_size.max(0.1);
+
+        let columns = ((bounds.w / cell_size).ceil() as usize).max(1);
+        let rows = ((bounds.h / cell_size).ceil() as usize).max(1);
+
+        Self {
+            origin: bounds.min(),
+            cell_size,
+            columns,
+            rows,
+            cells: vec![Vec::new(); columns * rows],
+            entries: Vec::new(),
+        }
+    }
+
+    /// Removes every entry while keeping the allocated cells around for the next rebuild.
+    pub fn clear(&mut self) {
+        for cell in &mut self.cells {
+            cell.clear();
+        }
+
+        self.entries.clear();
+    }
+
+    pub fn insert(&mut self, entity: Entity, pos: Vec2, radius: f32) {
+        let index = self.entries.len() as u32;
+
+        self.entries.push(SpatialEntry {
+            entity,
+            pos,
+            radius,
+        });
+
+        let ((min_column, min_row), (max_column, max_row)) = self.cell_range(pos, radius);
+
+        for row in min_row..=max_row {
+            for column in min_column..=max_column {
+                self.cells[row * self.columns + column].push(index);
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns every entry whose circle overlaps the given circle.
+    pub fn query(&self, pos: Vec2, radius: f32) -> impl Iterator<Item = &SpatialEntry> {
+        let ((min_column, min_row), (max_column, max_row)) = self.cell_range(pos, radius);
+
+        (min_row..=max_row)
+            .flat_map(move |row| (min_column..=max_column).map(move |column| (column, row)))
+            .flat_map(move |(column, row)| {
+                self.cells[row * self.columns + column]
+                    .iter()
+                    .map(move |&index| (column, row, &self.entries[index as usize]))
+            })
+            .filter(move |(column, row, entry)| {
+                // an entry spanning several cells is only reported from the first one visited
+                let ((entry_column, entry_row), _) = self.cell_range(entry.pos, entry.radius);
+                *column == entry_column.max(min_column) && *row == entry_row.max(min_row)
+            })
+            .map(|(_, _, entry)| entry)
+            .filter(move |entry| {
+                let radius_sum = radius + entry.radius;
+                (entry.pos - pos).magnitude_sq() < radius_sum * radius_sum
+            })
+    }
+
+    /// First and last cell covered by the bounding box of a circle.
+    fn cell_range(&self, pos: Vec2, radius: f32) -> ((usize, usize), (usize, usize)) {
+        let reach = Vec2::new(radius.max(0.0), radius.max(0.0));
+
+        (self.cell_coords(pos - reach), self.cell_coords(pos + reach))
+    }
+
+    /// Positions outside the grid are clamped into the border cells.
+    fn cell_coords(&self, pos: Vec2) -> (usize, usize) {
+        let local = (pos - self.origin) / self.cell_size;
+
+        let column = (local.x.floor().max(0.0) as usize).min(self.columns - 1);
+        let row = (local.y.floor().max(0.0) as usize).min(self.rows - 1);
+
+        (column, row)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hecs::World;
+
+    fn hash() -> SpatialHash {
+        SpatialHash::new(&Rect::new(0.0, 0.0, 40.0, 40.0), 4.0)
+    }
+
+    fn found(hash: &SpatialHash, pos: Vec2, radius: f32) -> Vec<Entity> {
+        hash.query(pos, radius).map(|entry| entry.entity).collect()
+    }
+
+    #[test]
+    fn finds_entries_across_cell_borders() {
+        let mut world = World::new();
+        let entity = world.spawn(());
+
+        let mut hash = hash();
+        hash.insert(entity, Vec2::new(3.9, 3.9), 0.5);
+
+        assert_eq!(found(&hash, Vec2::new(4.3, 4.3), 0.2), vec![entity]);
+        assert_eq!(found(&hash, Vec2::new(3.7, 4.3), 0.1), vec![entity]);
+        assert!(found(&hash, Vec2::new(5.0, 5.0), 0.2).is_empty());
+    }
+
+    #[test]
+    fn finds_large_entries_from_distant_cells() {
+        let mut world = World::new();
+        let large = world.spawn(());
+        let small = world.spawn(());
+
+        let mut hash = hash();
+        hash.insert(large, Vec2::new(20.0, 20.0), 15.0);
+        hash.insert(small, Vec2::new(2.0, 2.0), 0.5);
+
+        assert_eq!(found(&hash, Vec2::new(33.0, 20.0), 0.5), vec![large]);
+        assert_eq!(found(&hash, Vec2::new(2.0, 2.0), 0.1), vec![small]);
+        assert!(found(&hash, Vec2::new(38.0, 38.0), 0.5).is_empty());
+    }
+
+    #[test]
+    fn reports_each_entry_once() {
+        let mut world = World::new();
+        let entity = world.spawn(());
+
+        let mut hash = hash();
+        hash.insert(entity, Vec2::new(20.0, 20.0), 10.0);
+
+        assert_eq!(found(&hash, Vec2::new(20.0, 20.0), 12.0), vec![entity]);
+        assert_eq!(found(&hash, Vec2::new(25.0, 25.0), 1.0), vec![entity]);
+    }
+
+    #[test]
+    fn clamps_entries_outside_the_grid() {
+        let mut world = World::new();
+        let entity = world.spawn(());
+
+        let mut hash = hash();
+        hash.insert(entity, Vec2::new(-1.0, 41.0), 1.5);
+
+        assert_eq!(found(&hash, Vec2::new(0.2, 39.8), 0.5), vec![entity]);
+    }
+
+    #[test]
+    fn clear_removes_every_entry() {
+        let mut world = World::new();
+        let entity = world.spawn(());
+
+        let mut hash = hash();
+        hash.insert(entity, Vec2::new(20.0, 20.0), 10.0);
+        hash.clear();
+
+        assert!(hash.is_empty());
+        assert!(found(&hash, Vec2::new(20.0, 20.0), 1.0).is_empty());
+    }
+}
@@ -3,6 +3,7 @@ use std::{
     ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
 };
 
+use rand::Rng;
 use serde::Deserialize;
 
 #[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
@@ -23,8 +24,8 @@ impl Vec2 {
         }
     }
 
-    pub fn random_unit() -> Self {
-        let angle = rand::random::<f32>() * 2.0 * std::f32::consts::PI;
+    pub fn random_unit(rng: &mut impl Rng) -> Self {
+        let angle = rng.random::<f32>() * 2.0 * std::f32::consts::PI;
         Self::from_polar(1.0, angle)
     }
 
@@ -4,6 +4,7 @@ use crate::effects::core_types::{
 use std::{
     ops::{Add, Mul},
     sync::{
+        Mutex,
         atomic::{AtomicBool, Ordering},
         mpsc,
     },
@@ -22,7 +23,9 @@ where
     changed: AtomicBool,
     base_effect: EffectAction<T, TAdd, TMul>,
     effect_receptivity: f32,
-    rx: mpsc::Receiver<UpdateEffects>,
+    apply_effect: fn(&EffectAction<T, TAdd, TMul>, &mut T, f32),
+    // wrapped so values can be stored as ECS components, which have to be Sync
+    rx: Mutex<mpsc::Receiver<UpdateEffects>>,
     tx: mpsc::Sender<UpdateEffects>,
     effects: Vec<EffectMain<T, TAdd, TMul>>,
 }
@@ -41,7 +44,8 @@ where
             changed: AtomicBool::new(false),
             base_effect: EffectAction::None,
             effect_receptivity,
-            rx,
+            apply_effect: EffectAction::apply,
+            rx: Mutex::new(rx),
             tx,
             effects: Vec::new(),
         }
@@ -53,6 +57,7 @@ where
 
     pub fn set_base(&mut self, value: T) {
         self.base = value;
+        self.recalculate();
     }
 
     pub fn get_base(&self) -> T {
@@ -61,7 +66,7 @@ where
 
     pub fn set_receptivity(&mut self, effect_receptivity: f32) {
         if effect_receptivity != self.effect_receptivity {
-            self.rx.try_iter();
+            self.receiver().try_iter();
             self.effect_receptivity = effect_receptivity;
             self.effects
                 .retain(|effect| effect.action.upgrade().is_some());
@@ -78,7 +83,7 @@ where
     }
 
     pub fn get(&mut self) -> T {
-        if self.rx.try_iter().next().is_some() {
+        if self.receiver().try_iter().next().is_some() {
             self.effects
                 .retain(|effect| effect.action.upgrade().is_some());
             self.recalculate();
@@ -86,6 +91,10 @@ where
         self.value
     }
 
+    fn receiver(&mut self) -> &mpsc::Receiver<UpdateEffects> {
+        self.rx.get_mut().unwrap_or_else(|err| err.into_inner())
+    }
+
     fn recalculate(&mut self) {
         self.value = self.base;
         self.base_effect.apply_raw(&mut self.value);
@@ -102,7 +111,11 @@ where
                 if let Some(effect_ref) = effect.action.upgrade() {
                     let effect_ref = effect_ref.load();
                     if effect_ref.0 {
-                        effect_ref.1.apply(&mut self.value, self.effect_receptivity);
+                        (self.apply_effect)(
+                            &effect_ref.1,
+                            &mut self.value,
+                            self.effect_receptivity,
+                        );
                     } else {
                         effect_ref.1.apply_raw(&mut self.value);
                     }
@@ -112,7 +125,7 @@ where
             } else if let Some(effect_ref) = effect.action.upgrade() {
                 let effect_ref = effect_ref.load();
                 if effect_ref.0 {
-                    effect_ref.1.apply(&mut self.value, self.effect_receptivity);
+                    (self.apply_effect)(&effect_ref.1, &mut self.value, self.effect_receptivity);
                 } else {
                     effect_ref.1.apply_raw(&mut self.value);
                 }
@@ -124,3 +137,13 @@ where
         self.changed.store(true, Ordering::Relaxed);
     }
 }
+
+impl Value<f32> {
+    /// Value whose receptivity scales multipliers with `EffectAction::apply_scaled`.
+    pub fn scaled(base: f32, effect_receptivity: f32) -> Self {
+        Self {
+            apply_effect: EffectAction::apply_scaled,
+            ..Self::new(base, effect_receptivity)
+        }
+    }
+}